@@ -1,7 +1,7 @@
 use std::f64::consts::PI;
 
 use bevy::prelude::*;
-use bevy_prototype_lyon::{entity::ShapeBundle, prelude::*};
+use bevy_prototype_lyon::{entity::ShapeBundle, ops::PathMorph, prelude::*};
 use bevy::color::palettes::css::*;
 
 fn main() {
@@ -38,16 +38,22 @@ fn change_draw_mode_system(mut query: Query<(&mut Fill, &mut Stroke)>, time: Res
 }
 
 fn change_number_of_sides(mut query: Query<&mut Path>, time: Res<Time>) {
-    let sides = ((time.elapsed_secs_f64() - PI * 2.5).sin() * 2.5 + 5.5).round() as usize;
-
-    for mut path in query.iter_mut() {
-        let polygon = shapes::RegularPolygon {
+    let sides = (time.elapsed_secs_f64() - PI * 2.5).sin() * 2.5 + 5.5;
+    let polygon = |sides| {
+        ShapePath::build_as(&shapes::RegularPolygon {
             sides,
             feature: shapes::RegularPolygonFeature::Radius(200.0),
             ..shapes::RegularPolygon::default()
-        };
+        })
+    };
+    // Smoothly morph between the polygons with the closest numbers of sides.
+    let morph = PathMorph::new(
+        &polygon(sides.floor() as usize),
+        &polygon(sides.ceil() as usize),
+    );
 
-        *path = ShapePath::build_as(&polygon);
+    for mut path in query.iter_mut() {
+        *path = morph.at(sides.fract() as f32);
     }
 }
 
@@ -10,9 +10,6 @@ fn main() {
         .run();
 }
 
-#[derive(Component)]
-struct Name(String);
-
 #[derive(Component)]
 struct BlacksmithMarker;
 
@@ -39,7 +36,7 @@ fn setup_system(mut commands: Commands) {
     commands.spawn(Camera2d);
 
     commands
-        .spawn((Name("Blacksmith".to_owned()), BlacksmithMarker))
+        .spawn((Name::new("Blacksmith"), BlacksmithMarker))
         .insert(spatial_components(Transform::from_translation(
             Vec3::new(-50., 0., 0.),
         )))
@@ -72,7 +69,7 @@ fn setup_system(mut commands: Commands) {
         });
 
     commands
-        .spawn((Name("Shack".to_owned()), ToolShackMarker))
+        .spawn((Name::new("Shack"), ToolShackMarker))
         .insert(spatial_components(Transform {
             translation: Vec3::new(375., 0., 0.),
             scale: Vec3::new(0.1, 0.1, 1.),
@@ -1,4 +1,4 @@
-use bevy::{color::{Color, ColorToComponents, LinearRgba}, math::Vec2, prelude::default, reflect::Reflect};
+use bevy::{color::{Alpha, Color, ColorToComponents, LinearRgba}, math::Vec2, prelude::default, reflect::Reflect};
 use lyon_algorithms::geom::euclid::approxeq::ApproxEq;
 
 use crate::render::GradientMaterialUniform;
@@ -48,12 +48,27 @@ impl Brush {
                     end: end.into(),
                     start_pos,
                     end_pos,
+                    ..default()
                 }
             }
         }
     }
 }
 
+impl Brush {
+    /// Returns `true` if everything painted with this brush is fully
+    /// transparent.
+    #[must_use] pub fn is_transparent(&self) -> bool {
+        match self {
+            Self::Color(color) => color.alpha() == 0.0,
+            Self::Gradient(Gradient::Linear(linear)) => {
+                !linear.stops.is_empty()
+                    && linear.stops.iter().all(|stop| stop.color.alpha() == 0.0)
+            }
+        }
+    }
+}
+
 impl Default for Brush {
     fn default() -> Self {
         Self::Color(default())
@@ -136,15 +151,15 @@ impl LinearGradient {
         let vec = self.end - self.start;
         let length = vec.length();
         let posing_vec = pos - self.start;
-        if !length.approx_eq(&0.) {
+        if length.approx_eq(&0.) {
+            0.
+        } else {
             let product = vec.dot(posing_vec);
             product / (length * length)
-        } else {
-            0.
         }
     }
 }
-#[derive(Clone, Copy, PartialEq, PartialOrd)]
+#[derive(Clone, Copy, PartialEq)]
 struct NonNan(f32);
 impl NonNan {
     fn new_checked(val: f32) -> Option<Self> {
@@ -156,9 +171,14 @@ impl NonNan {
     }
 }
 impl Eq for NonNan {}
+impl PartialOrd for NonNan {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
 impl Ord for NonNan {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.partial_cmp(other).unwrap()
+        self.0.partial_cmp(&other.0).unwrap()
     }
 }
 impl Brusher for LinearGradient {
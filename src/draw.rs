@@ -1,9 +1,12 @@
 //! Types for defining shape color and options.
 
-use bevy::{ecs::component::Component, color::Color, reflect::Reflect, prelude::ReflectComponent};
-use lyon_tessellation::{FillOptions, StrokeOptions};
+use bevy::{
+    asset::Handle, color::Color, ecs::component::Component, image::Image, math::Vec2,
+    prelude::ReflectComponent, reflect::Reflect,
+};
+use lyon_tessellation::{self as tess, FillOptions, StrokeOptions};
 
-use crate::brush::Brush;
+use crate::{brush::Brush, entity::Path, geometry::Geometry};
 
 /// Defines the fill options for the lyon tessellator and color of the generated
 /// vertices.
@@ -36,6 +39,18 @@ pub struct Stroke {
     #[reflect(ignore)]
     pub options: StrokeOptions,
     pub brush: Brush,
+    pub alignment: StrokeAlignment,
+    /// Drawn at the first vertex of each open sub-path.
+    #[reflect(ignore)]
+    pub start_marker: Option<Marker>,
+    /// Drawn at every vertex that is neither a start nor an end vertex.
+    #[reflect(ignore)]
+    pub mid_marker: Option<Marker>,
+    /// Drawn at the last vertex of each open sub-path.
+    #[reflect(ignore)]
+    pub end_marker: Option<Marker>,
+    /// When set, the stroke is drawn with this image instead of the brush.
+    pub texture: Option<StrokeTexture>,
 }
 
 impl Stroke {
@@ -45,6 +60,7 @@ impl Stroke {
         Self {
             options: StrokeOptions::default().with_line_width(line_width),
             brush: brush.into(),
+            ..Self::default()
         }
     }
 
@@ -54,6 +70,368 @@ impl Stroke {
         Self {
             options: StrokeOptions::default(),
             brush: color.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Returns the stroke with the given [`StrokeAlignment`].
+    #[must_use]
+    pub fn with_alignment(mut self, alignment: StrokeAlignment) -> Self {
+        self.alignment = alignment;
+        self
+    }
+
+    /// Returns the stroke drawn with the given [`StrokeTexture`].
+    #[must_use]
+    pub fn with_texture(mut self, texture: StrokeTexture) -> Self {
+        self.texture = Some(texture);
+        self
+    }
+
+    /// Returns the stroke with the given marker at the start of open
+    /// sub-paths.
+    #[must_use]
+    pub fn with_start_marker(mut self, marker: Marker) -> Self {
+        self.start_marker = Some(marker);
+        self
+    }
+
+    /// Returns the stroke with the given marker at its inner vertices.
+    #[must_use]
+    pub fn with_mid_marker(mut self, marker: Marker) -> Self {
+        self.mid_marker = Some(marker);
+        self
+    }
+
+    /// Returns the stroke with the given marker at the end of open sub-paths.
+    #[must_use]
+    pub fn with_end_marker(mut self, marker: Marker) -> Self {
+        self.end_marker = Some(marker);
+        self
+    }
+}
+
+/// A decoration filled at the vertices of a stroked path, like an arrowhead.
+///
+/// The geometry is defined with the vertex at the origin and the path
+/// direction along the positive X axis. End and mid markers point in the
+/// direction of travel, start markers point away from the path, so the same
+/// arrowhead can be used at both ends of a line.
+///
+/// # Example
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_prototype_lyon::prelude::*;
+/// #
+/// let arrowhead = shapes::Polygon {
+///     points: vec![Vec2::ZERO, Vec2::new(-3.0, 1.5), Vec2::new(-3.0, -1.5)],
+///     closed: true,
+/// };
+/// let stroke = Stroke::new(Color::BLACK, 2.0).with_end_marker(Marker::new(&arrowhead));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Marker {
+    /// The outline of the marker.
+    pub path: tess::path::Path,
+    /// Whether the marker is scaled by the line width of the stroke, in which
+    /// case one unit of the geometry equals one line width.
+    pub scale_with_line_width: bool,
+}
+
+impl Marker {
+    /// Creates a marker, scaled with the line width, from the given geometry.
+    #[must_use]
+    pub fn new(geometry: &impl Geometry) -> Self {
+        let mut b = tess::path::Path::builder();
+        geometry.add_geometry(&mut b);
+        Self {
+            path: b.build(),
+            scale_with_line_width: true,
+        }
+    }
+
+    /// Returns the marker with the given scaling behavior.
+    #[must_use]
+    pub fn with_scale_with_line_width(mut self, scale_with_line_width: bool) -> Self {
+        self.scale_with_line_width = scale_with_line_width;
+        self
+    }
+}
+
+impl PartialEq for Marker {
+    fn eq(&self, other: &Self) -> bool {
+        self.scale_with_line_width == other.scale_with_line_width
+            && self.path.iter().eq(other.path.iter())
+    }
+}
+
+/// An image mapped along a stroke, like a rope or a road.
+///
+/// The U texture coordinate follows the distance along the path, while the V
+/// coordinate goes across the line width.
+#[allow(missing_docs)]
+#[derive(Debug, Clone, PartialEq, Reflect)]
+pub struct StrokeTexture {
+    pub image: Handle<Image>,
+    pub mode: StrokeTextureMode,
+}
+
+impl StrokeTexture {
+    /// Creates a texture repeated every `length` units along the path. See
+    /// [`StrokeTextureMode::Tile`] for lengths that are not positive.
+    #[must_use]
+    pub fn tiled(image: Handle<Image>, length: f32) -> Self {
+        Self {
+            image,
+            mode: StrokeTextureMode::Tile(length),
+        }
+    }
+
+    /// Creates a texture stretched once along each sub-path.
+    #[must_use]
+    pub fn stretched(image: Handle<Image>) -> Self {
+        Self {
+            image,
+            mode: StrokeTextureMode::Stretch,
+        }
+    }
+}
+
+/// Defines how a [`StrokeTexture`] covers the length of the path.
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+pub enum StrokeTextureMode {
+    /// The image is repeated every given number of units along the path.
+    ///
+    /// The length should be positive. Other lengths, including NaN, are not
+    /// an error: the first column of the image is stretched along the whole
+    /// path instead.
+    Tile(f32),
+    /// The image is stretched once along each sub-path.
+    Stretch,
+}
+
+/// Defines where the stroke is drawn relative to the path.
+///
+/// Only closed sub-paths are affected: open sub-paths have no inside or
+/// outside, so their stroke is always centered.
+///
+/// `Inside` and `Outside` offset the path by half the line width each time
+/// the shape is meshed, which costs much more than tessellating the stroke
+/// for paths with many vertices, or whose offset rings cross. Paths that
+/// change every frame are cheaper to draw with `Center`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+pub enum StrokeAlignment {
+    /// The stroke is centered on the path, as lyon draws it.
+    #[default]
+    Center,
+    /// The stroke is drawn entirely inside the filled region.
+    Inside,
+    /// The stroke is drawn entirely outside the filled region.
+    Outside,
+}
+
+/// Draws only part of the [`Path`] of a shape, to animate
+/// a line drawing itself or a loading spinner.
+///
+/// `start` and `end` are fractions of the length of the path, between `0.0`
+/// and `1.0`. `offset` is added to both and wraps around the end of the path,
+/// which suits closed paths. Both the fill and the stroke use the trimmed
+/// path.
+///
+/// Nothing is drawn when `end` is before `start`. A NaN `start`, `end` or
+/// `offset` is read as `0.0`, `1.0` and `0.0` respectively.
+///
+/// # Example
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_prototype_lyon::prelude::*;
+/// #
+/// fn spin(time: Res<Time>, mut query: Query<&mut PathTrim>) {
+///     for mut trim in &mut query {
+///         trim.offset = time.elapsed_secs() * 0.5;
+///     }
+/// }
+/// # bevy::ecs::system::assert_is_system(spin);
+/// ```
+#[derive(Component, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct PathTrim {
+    /// The start of the drawn part, as a fraction of the length of the path.
+    pub start: f32,
+    /// The end of the drawn part, as a fraction of the length of the path.
+    pub end: f32,
+    /// Shifts the drawn part along the path, as a fraction of its length.
+    pub offset: f32,
+}
+
+impl Default for PathTrim {
+    fn default() -> Self {
+        Self {
+            start: 0.0,
+            end: 1.0,
+            offset: 0.0,
+        }
+    }
+}
+
+impl PathTrim {
+    /// Creates a trim drawing the path from `start` to `end`.
+    #[must_use]
+    pub fn new(start: f32, end: f32) -> Self {
+        Self {
+            start,
+            end,
+            offset: 0.0,
+        }
+    }
+
+    /// Returns the trim with the given offset.
+    #[must_use]
+    pub fn with_offset(mut self, offset: f32) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Returns the part of `path` drawn with this trim.
+    #[must_use]
+    pub fn apply(&self, path: &Path) -> Path {
+        // `clamp` panics on NaN bounds, which animations can produce.
+        let or = |value: f32, default: f32| if value.is_nan() { default } else { value };
+        let start = or(self.start, 0.0).clamp(0.0, 1.0);
+        let end = or(self.end, 1.0).clamp(start, 1.0);
+        if end - start >= 1.0 {
+            return Path(path.0.clone());
+        }
+
+        let shift = or(self.offset, 0.0).rem_euclid(1.0);
+        let (start, end) = (start + shift, end + shift);
+        let measure = path.measure();
+        let length = measure.length();
+        let mut b = tess::path::Path::builder();
+        measure.split_into(start * length..end.min(1.0) * length, &mut b);
+        if end > 1.0 {
+            // The part past the end of the path continues from its start.
+            measure.split_into((start.max(1.0) - 1.0) * length..(end - 1.0) * length, &mut b);
+        }
+        Path(b.build())
+    }
+}
+
+/// Moves a shape so that a point of the bounding box of its [`Path`] is at
+/// the origin of its transform, like the `Anchor` of sprites.
+///
+/// The point is relative to the size of the bounding box: `(-0.5, -0.5)` is
+/// its bottom left corner and `(0.5, 0.5)` its top right corner. The mesh,
+/// its gradients and hit tests are moved, but not the `Path` itself. Shapes
+/// without an anchor are drawn where their path is.
+///
+/// The bounding box is the one of the whole path, before any [`PathTrim`],
+/// and does not include the stroke.
+///
+/// # Example
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_prototype_lyon::prelude::*;
+/// #
+/// fn my_system(mut commands: Commands) {
+///     let svg = shapes::SvgPathShape {
+///         svg_path_string: "M 0 0 L 40 0 L 20 30 Z".to_owned(),
+///         svg_doc_size_in_px: Vec2::new(40.0, 30.0),
+///     };
+///     commands.spawn((
+///         ShapeBundle {
+///             path: GeometryBuilder::build_as(&svg),
+///             ..default()
+///         },
+///         Fill::brush(Color::WHITE),
+///         ShapeAnchor::BOTTOM_LEFT,
+///     ));
+/// }
+/// # bevy::ecs::system::assert_is_system(my_system);
+/// ```
+#[derive(Component, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct ShapeAnchor(pub Vec2);
+
+#[allow(missing_docs)]
+impl ShapeAnchor {
+    pub const BOTTOM_LEFT: Self = Self(Vec2::new(-0.5, -0.5));
+    pub const BOTTOM_CENTER: Self = Self(Vec2::new(0.0, -0.5));
+    pub const BOTTOM_RIGHT: Self = Self(Vec2::new(0.5, -0.5));
+    pub const CENTER_LEFT: Self = Self(Vec2::new(-0.5, 0.0));
+    pub const CENTER: Self = Self(Vec2::ZERO);
+    pub const CENTER_RIGHT: Self = Self(Vec2::new(0.5, 0.0));
+    pub const TOP_LEFT: Self = Self(Vec2::new(-0.5, 0.5));
+    pub const TOP_CENTER: Self = Self(Vec2::new(0.0, 0.5));
+    pub const TOP_RIGHT: Self = Self(Vec2::new(0.5, 0.5));
+}
+
+impl ShapeAnchor {
+    /// Returns the translation moving the anchor of `path` to the origin.
+    #[must_use]
+    pub fn offset(&self, path: &Path) -> Vec2 {
+        let aabb = path.aabb();
+        -(aabb.center() + self.0 * aabb.size())
+    }
+}
+
+impl Default for ShapeAnchor {
+    fn default() -> Self {
+        Self::CENTER
+    }
+}
+
+impl From<Vec2> for ShapeAnchor {
+    fn from(anchor: Vec2) -> Self {
+        Self(anchor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::math::{Rect, Vec2};
+
+    use super::{PathTrim, ShapeAnchor};
+    use crate::{entity::Path, geometry::GeometryBuilder, shapes};
+
+    fn trimmed_length(trim: PathTrim) -> f32 {
+        let line = GeometryBuilder::build_as(&shapes::Line(Vec2::ZERO, Vec2::new(10.0, 0.0)));
+        trim.apply(&line).length()
+    }
+
+    #[test]
+    fn nan_trims() {
+        assert!((trimmed_length(PathTrim::new(f32::NAN, 0.5)) - 5.0).abs() < 1e-4);
+        assert!((trimmed_length(PathTrim::new(0.5, f32::NAN)) - 5.0).abs() < 1e-4);
+        assert!((trimmed_length(PathTrim::new(f32::NAN, f32::NAN)) - 10.0).abs() < 1e-4);
+        let offset = PathTrim::new(0.2, 0.6).with_offset(f32::NAN);
+        assert!((trimmed_length(offset) - 4.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn inverted_trims() {
+        assert!(trimmed_length(PathTrim::new(0.8, 0.2)) < 1e-4);
+        assert!(trimmed_length(PathTrim::new(0.8, 0.2).with_offset(0.5)) < 1e-4);
+        assert!((trimmed_length(PathTrim::new(-1.0, 2.0)) - 10.0).abs() < 1e-4);
+    }
+
+
+    #[test]
+    fn anchor_offsets() {
+        let anchors = [ShapeAnchor::CENTER, ShapeAnchor::BOTTOM_LEFT, ShapeAnchor::TOP_RIGHT];
+        let empty = Path(lyon_tessellation::path::Path::new());
+        for anchor in anchors {
+            assert_eq!(anchor.offset(&empty), Vec2::ZERO);
+        }
+        let rect = shapes::Rectangle::new(Rect::new(10.0, 20.0, 30.0, 60.0));
+        let path = GeometryBuilder::build_as(&rect);
+        let offsets = anchors.map(|anchor| anchor.offset(&path));
+        let expected = [Vec2::new(-20.0, -40.0), Vec2::new(-10.0, -20.0), Vec2::new(-30.0, -60.0)];
+        for (offset, expected) in offsets.into_iter().zip(expected) {
+            assert!(offset.abs_diff_eq(expected, 1e-4));
         }
     }
 }
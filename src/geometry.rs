@@ -1,6 +1,7 @@
 //! Types for defining and using geometries.
 
-use lyon_tessellation::path::path::Builder;
+use bevy::math::{Affine2, Vec2};
+use lyon_tessellation::{math::Transform, path::path::Builder};
 
 use crate::entity::Path;
 
@@ -47,8 +48,158 @@ use crate::entity::Path;
 pub trait Geometry {
     /// Adds the geometry of the shape to the given Lyon path `Builder`.
     fn add_geometry(&self, b: &mut Builder);
+
+    /// Returns this geometry moved by `offset`.
+    #[must_use]
+    fn translated(self, offset: Vec2) -> Transformed<Self>
+    where
+        Self: Sized,
+    {
+        self.transformed(Affine2::from_translation(offset))
+    }
+
+    /// Returns this geometry rotated counter-clockwise by `angle` radians
+    /// around the origin.
+    #[must_use]
+    fn rotated(self, angle: f32) -> Transformed<Self>
+    where
+        Self: Sized,
+    {
+        self.transformed(Affine2::from_angle(angle))
+    }
+
+    /// Returns this geometry scaled by `scale` from the origin.
+    #[must_use]
+    fn scaled(self, scale: Vec2) -> Transformed<Self>
+    where
+        Self: Sized,
+    {
+        self.transformed(Affine2::from_scale(scale))
+    }
+
+    /// Returns this geometry with `transform` applied to it.
+    #[must_use]
+    fn transformed(self, transform: Affine2) -> Transformed<Self>
+    where
+        Self: Sized,
+    {
+        Transformed {
+            geometry: self,
+            transform,
+        }
+    }
+}
+
+/// A geometry with an affine transform applied to it, created with
+/// [`Geometry::transformed`] and the similar methods.
+///
+/// Transforms that mirror the geometry also reverse the direction of its
+/// outlines.
+///
+/// # Example
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_prototype_lyon::prelude::*;
+/// #
+/// let wheel = shapes::Circle {
+///     radius: 1.0,
+///     ..default()
+/// };
+/// let cart = (
+///     shapes::Rectangle::new(Rect::new(-3.0, 0.0, 3.0, 2.0)),
+///     [-2.0, 2.0].map(|x| wheel.translated(Vec2::new(x, 0.0))),
+/// );
+///
+/// let path = GeometryBuilder::build_as(&cart.rotated(std::f32::consts::FRAC_PI_2));
+///
+/// // The cart spans -3..3 along X and -1..2 along Y before the rotation.
+/// let bounds = path.aabb();
+/// assert!(bounds.min.abs_diff_eq(Vec2::new(-2.0, -3.0), 1e-4));
+/// assert!(bounds.max.abs_diff_eq(Vec2::new(1.0, 3.0), 1e-4));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Transformed<G> {
+    /// The transformed geometry.
+    pub geometry: G,
+    /// The transform applied to the geometry.
+    pub transform: Affine2,
+}
+
+impl<G: Geometry> Geometry for Transformed<G> {
+    fn add_geometry(&self, b: &mut Builder) {
+        let mut builder = Builder::new();
+        self.geometry.add_geometry(&mut builder);
+        let Affine2 {
+            matrix2,
+            translation,
+        } = self.transform;
+        let transform = Transform::new(
+            matrix2.x_axis.x,
+            matrix2.x_axis.y,
+            matrix2.y_axis.x,
+            matrix2.y_axis.y,
+            translation.x,
+            translation.y,
+        );
+        let path = builder.build().transformed(&transform);
+        b.extend_from_paths(&[path.as_slice()]);
+    }
 }
 
+impl<G: Geometry + ?Sized> Geometry for &G {
+    fn add_geometry(&self, b: &mut Builder) {
+        (**self).add_geometry(b);
+    }
+}
+
+impl<G: Geometry + ?Sized> Geometry for Box<G> {
+    fn add_geometry(&self, b: &mut Builder) {
+        (**self).add_geometry(b);
+    }
+}
+
+impl<G: Geometry> Geometry for [G] {
+    fn add_geometry(&self, b: &mut Builder) {
+        for geometry in self {
+            geometry.add_geometry(b);
+        }
+    }
+}
+
+impl<G: Geometry, const N: usize> Geometry for [G; N] {
+    fn add_geometry(&self, b: &mut Builder) {
+        self.as_slice().add_geometry(b);
+    }
+}
+
+impl<G: Geometry> Geometry for Vec<G> {
+    fn add_geometry(&self, b: &mut Builder) {
+        self.as_slice().add_geometry(b);
+    }
+}
+
+macro_rules! impl_geometry_for_tuple {
+    ($($name:ident),+) => {
+        impl<$($name: Geometry),+> Geometry for ($($name,)+) {
+            #[allow(non_snake_case)]
+            fn add_geometry(&self, b: &mut Builder) {
+                let ($($name,)+) = self;
+                $($name.add_geometry(b);)+
+            }
+        }
+    };
+}
+
+impl_geometry_for_tuple!(A);
+impl_geometry_for_tuple!(A, B);
+impl_geometry_for_tuple!(A, B, C);
+impl_geometry_for_tuple!(A, B, C, D);
+impl_geometry_for_tuple!(A, B, C, D, E);
+impl_geometry_for_tuple!(A, B, C, D, E, F);
+impl_geometry_for_tuple!(A, B, C, D, E, F, G);
+impl_geometry_for_tuple!(A, B, C, D, E, F, G, H);
+
 /// Allows the creation of shapes using geometries added to a path builder.
 pub struct GeometryBuilder(Builder);
 
@@ -69,9 +220,8 @@ impl GeometryBuilder {
     /// #
     /// fn my_system(mut commands: Commands) {
     ///     let line = shapes::Line(Vec2::ZERO, Vec2::new(10.0, 0.0));
-    ///     let square = shapes::Rectangle {
-    ///         rect: Rect::from_center_size(Vec2::ZERO, Vec2::splat(100.0)),
-    ///     };
+    ///     let square =
+    ///         shapes::Rectangle::new(Rect::from_center_size(Vec2::ZERO, Vec2::splat(100.0)));
     ///     let mut builder = GeometryBuilder::new().add(&line).add(&square);
     ///
     ///     commands.spawn((
@@ -129,3 +279,31 @@ impl Default for GeometryBuilder {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy::math::{Rect, Vec2};
+    use lyon_tessellation::FillOptions;
+
+    use super::{Geometry, GeometryBuilder};
+    use crate::{ops::path_signed_area, shapes};
+
+    fn signed_area(shape: &impl Geometry) -> f32 {
+        path_signed_area(&GeometryBuilder::build_as(shape).0, FillOptions::DEFAULT_TOLERANCE)
+    }
+
+    #[test]
+    fn mirroring_reverses_winding() {
+        let rect = shapes::Rectangle::new(Rect::new(1.0, 2.0, 4.0, 6.0));
+        let area = signed_area(&rect);
+        assert!((area.abs() - 12.0).abs() < 1e-4);
+        // The signed area scales with the determinant, which is negative here.
+        for scale in [Vec2::new(-1.0, 1.0), Vec2::new(1.0, -2.0)] {
+            let mirrored = signed_area(&rect.scaled(scale));
+            assert!((mirrored - area * scale.x * scale.y).abs() < 1e-3);
+        }
+        // Mirroring both axes is a rotation, so the winding is kept.
+        assert!((signed_area(&rect.scaled(Vec2::splat(-1.0))) - area).abs() < 1e-4);
+        assert!((signed_area(&rect.rotated(1.0)) - area).abs() < 1e-4);
+    }
+}
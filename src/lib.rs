@@ -27,11 +27,15 @@ pub mod draw;
 pub mod entity;
 pub mod geometry;
 pub mod path;
+#[cfg(feature = "picking")]
+pub mod picking;
 pub mod plugin;
 pub mod render;
 pub mod shapes;
 pub mod brush;
 
+pub mod ops;
+mod primitives;
 mod utils;
 mod vertex;
 
@@ -43,12 +47,15 @@ pub mod prelude {
     };
 
     pub use crate::{
-        draw::{Fill, Stroke},
+        draw::{
+            Fill, Marker, PathTrim, ShapeAnchor, Stroke, StrokeAlignment, StrokeTexture,
+            StrokeTextureMode,
+        },
         entity::{Path, ShapeBundle},
         geometry::{Geometry, GeometryBuilder},
         path::{PathBuilder, ShapePath},
         plugin::ShapePlugin,
-        shapes::{self, RectangleOrigin, RegularPolygon, RegularPolygonFeature},
+        shapes::{self, RectangleOrigin, RegularPolygon, RegularPolygonFeature, Star, Winding},
         brush::*
     };
 }
@@ -0,0 +1,853 @@
+//! Boolean operations between filled paths.
+
+use std::{
+    cmp::Ordering,
+    collections::{HashMap, HashSet, VecDeque},
+};
+
+use bevy::log;
+use bevy::math::Vec2;
+use lyon_tessellation::{FillOptions, FillRule};
+
+use super::{build_path, flatten, Polyline};
+use crate::entity::Path;
+
+/// Intersection parameters closer than this to the end of a segment are
+/// snapped to it.
+const PARAM_EPSILON: f32 = 1e-5;
+
+/// The way two filled regions are combined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BooleanOp {
+    /// The region covered by either path.
+    Union,
+    /// The region covered by both paths.
+    Intersection,
+    /// The region covered by the first path but not by the second.
+    Difference,
+    /// The region covered by exactly one of the paths.
+    Xor,
+}
+
+impl BooleanOp {
+    fn apply(self, a: bool, b: bool) -> bool {
+        match self {
+            Self::Union => a || b,
+            Self::Intersection => a && b,
+            Self::Difference => a && !b,
+            Self::Xor => a != b,
+        }
+    }
+}
+
+impl Path {
+    /// Combines the filled regions of this path and `other`.
+    ///
+    /// Both paths are flattened with the tolerance of `options` and filled
+    /// with its fill rule; open sub-paths are considered closed. The outer
+    /// boundaries of the result are counter-clockwise and its holes are
+    /// clockwise, so it is filled the same way with both fill rules.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_prototype_lyon::prelude::*;
+    /// # use bevy_prototype_lyon::ops::BooleanOp;
+    /// #
+    /// let ground =
+    ///     GeometryBuilder::build_as(&shapes::Rectangle::new(Rect::new(-100.0, -50.0, 100.0, 0.0)));
+    /// let explosion = GeometryBuilder::build_as(&shapes::Circle {
+    ///     radius: 20.0,
+    ///     ..default()
+    /// });
+    ///
+    /// let crater = ground.boolean(&explosion, BooleanOp::Difference, &FillOptions::default());
+    /// # let inside = |p: Vec2| {
+    /// #     lyon_algorithms::hit_test::hit_test_path(
+    /// #         &tess::math::point(p.x, p.y),
+    /// #         crater.0.iter(),
+    /// #         FillRule::NonZero,
+    /// #         0.1,
+    /// #     )
+    /// # };
+    /// # assert!(inside(Vec2::new(50.0, -25.0)));
+    /// # assert!(!inside(Vec2::new(0.0, -10.0)));
+    /// ```
+    #[must_use]
+    pub fn boolean(&self, other: &Self, op: BooleanOp, options: &FillOptions) -> Self {
+        let shapes = [
+            flatten(&self.0, options.tolerance),
+            flatten(&other.0, options.tolerance),
+        ];
+        let fill_rule = options.fill_rule;
+        let rings = resolve(&shapes, |windings| {
+            op.apply(
+                is_inside(windings[0], fill_rule),
+                is_inside(windings[1], fill_rule),
+            )
+        });
+        Self(build_path(&rings))
+    }
+
+    /// Returns the region covered by this path or `other`, using the default
+    /// [`FillOptions`]. See [`boolean`](Self::boolean).
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Self {
+        self.boolean(other, BooleanOp::Union, &FillOptions::default())
+    }
+
+    /// Returns the region covered by both this path and `other`, using the
+    /// default [`FillOptions`]. See [`boolean`](Self::boolean).
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Self {
+        self.boolean(other, BooleanOp::Intersection, &FillOptions::default())
+    }
+
+    /// Returns the region covered by this path but not by `other`, using the
+    /// default [`FillOptions`]. See [`boolean`](Self::boolean).
+    #[must_use]
+    pub fn difference(&self, other: &Self) -> Self {
+        self.boolean(other, BooleanOp::Difference, &FillOptions::default())
+    }
+
+    /// Returns the region covered by exactly one of this path and `other`,
+    /// using the default [`FillOptions`]. See [`boolean`](Self::boolean).
+    #[must_use]
+    pub fn xor(&self, other: &Self) -> Self {
+        self.boolean(other, BooleanOp::Xor, &FillOptions::default())
+    }
+}
+
+/// Returns whether a point with the given winding number is filled.
+pub(crate) fn is_inside(winding: i32, fill_rule: FillRule) -> bool {
+    match fill_rule {
+        FillRule::EvenOdd => winding % 2 != 0,
+        FillRule::NonZero => winding != 0,
+    }
+}
+
+/// A directed segment of the outline of one of the input shapes.
+#[derive(Debug, Clone, Copy)]
+struct Edge {
+    from: Vec2,
+    to: Vec2,
+    shape: usize,
+}
+
+impl Edge {
+    /// Returns the edge turned a quarter turn clockwise around the origin,
+    /// which is exact and keeps the winding numbers.
+    fn rotated(self) -> Self {
+        let rotate = |p: Vec2| Vec2::new(p.y, -p.x);
+        Self {
+            from: rotate(self.from),
+            to: rotate(self.to),
+            ..self
+        }
+    }
+}
+
+/// The split edges lying on the same segment, whatever their direction and
+/// shape.
+struct Segment {
+    from: Vec2,
+    to: Vec2,
+    /// For each shape, how much its winding number increases when crossing
+    /// the segment from its right to its left.
+    winding_change: Vec<i32>,
+}
+
+/// Returns the outline of the region made of the points for which `inside`
+/// returns `true`, given the winding number of each shape at that point.
+///
+/// Every polyline of the shapes is considered closed. The returned rings have
+/// the region on their left.
+///
+/// The edges are split where they cross, and the segments they form bound
+/// faces with constant winding numbers. The winding numbers of the faces are
+/// derived from each other across the segments between them, so that every
+/// ring of the result closes even where rounding errors make the segments
+/// slightly overlap.
+pub(crate) fn resolve(shapes: &[Vec<Polyline>], inside: impl Fn(&[i32]) -> bool) -> Vec<Polyline> {
+    let edges = shapes
+        .iter()
+        .enumerate()
+        .flat_map(|(shape, rings)| ring_edges(rings, shape))
+        .collect::<Vec<_>>();
+    let edges = split_edges(&edges);
+
+    let mut segments: HashMap<_, Segment> = HashMap::new();
+    for edge in &edges {
+        let (from, to) = (key(edge.from), key(edge.to));
+        let forward = from < to;
+        let segment = segments
+            .entry(if forward { (from, to) } else { (to, from) })
+            .or_insert_with(|| Segment {
+                from: if forward { edge.from } else { edge.to },
+                to: if forward { edge.to } else { edge.from },
+                winding_change: vec![0; shapes.len()],
+            });
+        segment.winding_change[edge.shape] += if forward { 1 } else { -1 };
+    }
+    // Segments along which the edges cancel out bound nothing. Sorting makes
+    // the output independent of the hash map order.
+    let mut segments = segments
+        .into_iter()
+        .filter(|(_, segment)| segment.winding_change.iter().any(|&change| change != 0))
+        .collect::<Vec<_>>();
+    segments.sort_by_key(|&(key, _)| key);
+    let segments = segments
+        .into_iter()
+        .map(|(_, segment)| segment)
+        .collect::<Vec<_>>();
+
+    let faces = Faces::new(&segments);
+    let windings = faces.windings(&segments, &edges, shapes.len());
+    let mut kept = Vec::new();
+    for (i, segment) in segments.iter().enumerate() {
+        let left = &windings[faces.face_of[2 * i]];
+        let right = &windings[faces.face_of[2 * i + 1]];
+        match (inside(left), inside(right)) {
+            (true, false) => kept.push((segment.from, segment.to)),
+            (false, true) => kept.push((segment.to, segment.from)),
+            _ => {}
+        }
+    }
+
+    link_edges(&kept)
+}
+
+/// The faces bounded by the segments.
+///
+/// Half-edge `2 * i` goes along segment `i` and half-edge `2 * i + 1` goes
+/// back; each half-edge bounds the face on its left.
+struct Faces {
+    face_of: Vec<usize>,
+    edges: Vec<Vec<usize>>,
+}
+
+impl Faces {
+    fn new(segments: &[Segment]) -> Self {
+        let ends = |half_edge: usize| {
+            let segment = &segments[half_edge / 2];
+            if half_edge.is_multiple_of(2) {
+                (segment.from, segment.to)
+            } else {
+                (segment.to, segment.from)
+            }
+        };
+        let angle = |half_edge: usize| {
+            let (from, to) = ends(half_edge);
+            (to.y - from.y).atan2(to.x - from.x)
+        };
+
+        let mut outgoing: HashMap<_, Vec<usize>> = HashMap::new();
+        for half_edge in 0..2 * segments.len() {
+            outgoing
+                .entry(key(ends(half_edge).0))
+                .or_default()
+                .push(half_edge);
+        }
+        // Arriving at a point, the face on the left continues along the next
+        // half-edge leaving it clockwise from the way back.
+        let mut next = vec![0; 2 * segments.len()];
+        for around in outgoing.values_mut() {
+            around.sort_by(|&a, &b| angle(a).total_cmp(&angle(b)));
+            for (k, &half_edge) in around.iter().enumerate() {
+                next[half_edge ^ 1] = around[(k + around.len() - 1) % around.len()];
+            }
+        }
+
+        let mut face_of = vec![usize::MAX; next.len()];
+        let mut edges = Vec::new();
+        for start in 0..next.len() {
+            if face_of[start] != usize::MAX {
+                continue;
+            }
+            let mut face = Vec::new();
+            let mut half_edge = start;
+            while face_of[half_edge] == usize::MAX {
+                face_of[half_edge] = edges.len();
+                face.push(half_edge);
+                half_edge = next[half_edge];
+            }
+            edges.push(face);
+        }
+        Self { face_of, edges }
+    }
+
+    /// Returns the winding number of each shape in each face.
+    ///
+    /// A ray is only cast for the longest segment of each group of connected
+    /// segments; the other faces are reached by crossing segments.
+    fn windings(&self, segments: &[Segment], edges: &[Edge], shapes: usize) -> Vec<Vec<i32>> {
+        let indices = [
+            RayIndex::new(edges.to_vec()),
+            RayIndex::new(edges.iter().map(|edge| edge.rotated()).collect()),
+        ];
+        let mut order = (0..segments.len()).collect::<Vec<_>>();
+        let length = |i: usize| segments[i].from.distance_squared(segments[i].to);
+        order.sort_by(|&a, &b| length(b).total_cmp(&length(a)));
+
+        let mut windings: Vec<Option<Vec<i32>>> = vec![None; self.edges.len()];
+        let mut queue = VecDeque::new();
+        for i in order {
+            let face = self.face_of[2 * i];
+            if windings[face].is_some() {
+                continue;
+            }
+            windings[face] = Some(side_windings(&segments[i], &indices, shapes).0);
+            queue.push_back(face);
+            while let Some(face) = queue.pop_front() {
+                for &half_edge in &self.edges[face] {
+                    let other = self.face_of[half_edge ^ 1];
+                    if windings[other].is_some() {
+                        continue;
+                    }
+                    // Crossing a half-edge from its left to its right.
+                    let change = &segments[half_edge / 2].winding_change;
+                    let sign = if half_edge.is_multiple_of(2) { -1 } else { 1 };
+                    let crossed = windings[face]
+                        .iter()
+                        .flatten()
+                        .zip(change)
+                        .map(|(w, c)| w + sign * c)
+                        .collect();
+                    windings[other] = Some(crossed);
+                    queue.push_back(other);
+                }
+            }
+        }
+        windings
+            .into_iter()
+            .map(Option::unwrap_or_default)
+            .collect()
+    }
+}
+
+/// Returns the outline of the region made of the points for which `inside`
+/// returns `true`, given the winding number of `rings` at that point, like
+/// [`resolve`] for a single shape.
+///
+/// Rings that do not cross are inside or outside each other, which gives the
+/// winding numbers on both of their sides without splitting any edge.
+pub(crate) fn resolve_rings(rings: Vec<Polyline>, inside: impl Fn(i32) -> bool) -> Vec<Polyline> {
+    if has_crossings(&rings) {
+        return resolve(&[rings], |windings| inside(windings[0]));
+    }
+
+    let orientations = rings
+        .iter()
+        .map(|ring| {
+            let area = ring.signed_area();
+            i32::from(area > 0.0) - i32::from(area < 0.0)
+        })
+        .collect::<Vec<_>>();
+    rings
+        .iter()
+        .enumerate()
+        .filter_map(|(i, ring)| {
+            let outside = rings
+                .iter()
+                .zip(&orientations)
+                .enumerate()
+                .filter(|&(j, (other, _))| j != i && other.contains(ring.points[0]))
+                .map(|(_, (_, orientation))| orientation)
+                .sum::<i32>();
+            let counter_clockwise = match (inside(outside + orientations[i]), inside(outside)) {
+                (true, false) => true,
+                (false, true) => false,
+                _ => return None,
+            };
+            let mut points = ring.points.clone();
+            if counter_clockwise != (orientations[i] > 0) {
+                points.reverse();
+            }
+            Some(Polyline {
+                points,
+                closed: true,
+            })
+        })
+        .collect()
+}
+
+/// Returns `true` if edges of the rings cross, overlap or share a point, in
+/// which case the way the rings are nested does not tell their winding
+/// numbers.
+pub(crate) fn has_crossings(rings: &[Polyline]) -> bool {
+    let mut points = HashSet::new();
+    if !rings
+        .iter()
+        .flat_map(|ring| &ring.points)
+        .all(|&point| points.insert(key(point)))
+    {
+        return true;
+    }
+    let edges = ring_edges(rings, 0);
+    let split = split_edges(&edges);
+    split.len() != edges.len()
+        || split.iter().zip(&edges).any(|(a, b)| {
+            (key(a.from), key(a.to)) != (key(b.from), key(b.to))
+        })
+}
+
+fn ring_edges(rings: &[Polyline], shape: usize) -> Vec<Edge> {
+    rings
+        .iter()
+        .flat_map(|ring| {
+            let n = ring.points.len();
+            (0..n).map(move |i| Edge {
+                from: ring.points[i],
+                to: ring.points[(i + 1) % n],
+                shape,
+            })
+        })
+        .filter(|edge| edge.from != edge.to)
+        .collect()
+}
+
+/// Returns the winding numbers of each shape on the left and on the right of
+/// the segment.
+///
+/// `indices` holds the edges as they are and rotated a quarter turn; the one
+/// in which the segment is the most vertical is used, so that the middle of
+/// the segment is strictly between its ends vertically.
+fn side_windings(
+    segment: &Segment,
+    indices: &[RayIndex; 2],
+    shapes: usize,
+) -> (Vec<i32>, Vec<i32>) {
+    let mut edge = Edge {
+        from: segment.from,
+        to: segment.to,
+        shape: 0,
+    };
+    let delta = edge.to - edge.from;
+    let index = if delta.x.abs() > delta.y.abs() {
+        edge = edge.rotated();
+        &indices[1]
+    } else {
+        &indices[0]
+    };
+
+    // The ray going right from the middle of the segment crosses the same
+    // edges as from a point just next to the segment, on its right side if
+    // it goes up. The segment itself is only crossed from the other side.
+    let mid = edge.from.midpoint(edge.to);
+    let own = (key(edge.from), key(edge.to));
+    let ray_side = index.winding_numbers(mid, own, shapes);
+
+    let change = &segment.winding_change;
+    if edge.to.y > edge.from.y {
+        let left = ray_side.iter().zip(change).map(|(w, c)| w + c).collect();
+        (left, ray_side)
+    } else {
+        let right = ray_side.iter().zip(change).map(|(w, c)| w - c).collect();
+        (ray_side, right)
+    }
+}
+
+/// The edges sorted into horizontal bands, to only test the edges that a
+/// horizontal ray can cross.
+struct RayIndex {
+    edges: Vec<Edge>,
+    min_y: f32,
+    band_height: f32,
+    bands: Vec<Vec<usize>>,
+}
+
+impl RayIndex {
+    fn new(edges: Vec<Edge>) -> Self {
+        let (min_y, max_y) = edges
+            .iter()
+            .fold((f32::INFINITY, f32::NEG_INFINITY), |(lo, hi), e| {
+                (lo.min(e.from.y).min(e.to.y), hi.max(e.from.y).max(e.to.y))
+            });
+        #[allow(clippy::cast_sign_loss)] // The count is at least 1.
+        let count = (edges.len() as f32).sqrt().ceil().max(1.0) as usize;
+        let band_height = ((max_y - min_y) / count as f32).max(f32::MIN_POSITIVE);
+        let mut index = Self {
+            edges: Vec::new(),
+            min_y,
+            band_height,
+            bands: vec![Vec::new(); count],
+        };
+        for (i, edge) in edges.iter().enumerate() {
+            let first = index.band(edge.from.y.min(edge.to.y));
+            let last = index.band(edge.from.y.max(edge.to.y));
+            for band in &mut index.bands[first..=last] {
+                band.push(i);
+            }
+        }
+        index.edges = edges;
+        index
+    }
+
+    #[allow(clippy::cast_sign_loss)] // Points below `min_y` saturate to the first band.
+    fn band(&self, y: f32) -> usize {
+        (((y - self.min_y) / self.band_height) as usize).min(self.bands.len() - 1)
+    }
+
+    /// Returns the winding number of each shape around `point`, ignoring the
+    /// edges of the segment `own`, in both directions.
+    fn winding_numbers(&self, point: Vec2, own: (Key, Key), shapes: usize) -> Vec<i32> {
+        let mut windings = vec![0; shapes];
+        if self.edges.is_empty() {
+            return windings;
+        }
+        for &i in &self.bands[self.band(point.y)] {
+            let edge = self.edges[i];
+            let edge_key = (key(edge.from), key(edge.to));
+            if edge_key == own || edge_key == (own.1, own.0) {
+                continue;
+            }
+            let side = (edge.to - edge.from).perp_dot(point - edge.from);
+            if edge.from.y <= point.y && edge.to.y > point.y && side > 0.0 {
+                windings[edge.shape] += 1;
+            } else if edge.from.y > point.y && edge.to.y <= point.y && side < 0.0 {
+                windings[edge.shape] -= 1;
+            }
+        }
+        windings
+    }
+}
+
+/// Splits the edges at every intersection, so that edges only meet at their
+/// ends.
+fn split_edges(edges: &[Edge]) -> Vec<Edge> {
+    let mut splits = vec![Vec::new(); edges.len()];
+    let mut merged = HashMap::new();
+
+    // Only pairs of edges whose horizontal extents overlap are tested.
+    let mut order = (0..edges.len()).collect::<Vec<_>>();
+    let min_x = |i: usize| edges[i].from.x.min(edges[i].to.x);
+    order.sort_by(|&a, &b| min_x(a).total_cmp(&min_x(b)));
+    for (k, &i) in order.iter().enumerate() {
+        let max_x = edges[i].from.x.max(edges[i].to.x);
+        for &j in order[k + 1..].iter().take_while(|&&j| min_x(j) <= max_x) {
+            for meeting in intersections(edges[i], edges[j]) {
+                match meeting {
+                    Meeting::Split(edge, t, point) => {
+                        splits[if edge == 0 { i } else { j }].push((t, point));
+                    }
+                    Meeting::Merge(a, b) => merge(&mut merged, a, b),
+                }
+            }
+        }
+    }
+
+    let mut result = Vec::with_capacity(edges.len());
+    for (edge, mut points) in edges.iter().zip(splits) {
+        points.sort_by(|a, b| a.0.total_cmp(&b.0));
+        let mut from = find(&merged, edge.from);
+        for (_, to) in points.into_iter().chain([(1.0, edge.to)]) {
+            let to = find(&merged, to);
+            if key(to) != key(from) {
+                result.push(Edge { from, to, ..*edge });
+                from = to;
+            }
+        }
+    }
+    result
+}
+
+/// Returns the point that `point` was merged into, or `point` itself.
+fn find(merged: &HashMap<Key, Vec2>, mut point: Vec2) -> Vec2 {
+    while let Some(&into) = merged.get(&key(point)) {
+        point = into;
+    }
+    point
+}
+
+/// Merges `a` and `b`, and every point they were merged with, into the one
+/// with the smallest key.
+fn merge(merged: &mut HashMap<Key, Vec2>, a: Vec2, b: Vec2) {
+    let (a, b) = (find(merged, a), find(merged, b));
+    match key(a).cmp(&key(b)) {
+        Ordering::Less => merged.insert(key(b), a),
+        Ordering::Greater => merged.insert(key(a), b),
+        Ordering::Equal => None,
+    };
+}
+
+/// How two edges meet.
+enum Meeting {
+    /// The edge `0` or `1` is split at the given parameter and point.
+    Split(usize, f32, Vec2),
+    /// The edges cross next to an end of each; both ends are merged, or the
+    /// edges would cross without meeting.
+    Merge(Vec2, Vec2),
+}
+
+/// Returns the ways `a` and `b` meet.
+fn intersections(a: Edge, b: Edge) -> Vec<Meeting> {
+    let da = a.to - a.from;
+    let db = b.to - b.from;
+    let denom = da.perp_dot(db);
+    let offset = b.from - a.from;
+
+    if denom.abs() <= f32::EPSILON * da.length() * db.length() {
+        // Parallel edges only meet if they are collinear.
+        if offset.perp_dot(da).abs() > PARAM_EPSILON * da.length_squared() {
+            return Vec::new();
+        }
+        let mut result = Vec::new();
+        for point in [b.from, b.to] {
+            let t = (point - a.from).dot(da) / da.length_squared();
+            if t > PARAM_EPSILON && t < 1.0 - PARAM_EPSILON {
+                result.push(Meeting::Split(0, t, point));
+            }
+        }
+        for point in [a.from, a.to] {
+            let u = (point - b.from).dot(db) / db.length_squared();
+            if u > PARAM_EPSILON && u < 1.0 - PARAM_EPSILON {
+                result.push(Meeting::Split(1, u, point));
+            }
+        }
+        return result;
+    }
+
+    let t = offset.perp_dot(db) / denom;
+    let u = offset.perp_dot(da) / denom;
+    let range = -PARAM_EPSILON..=1.0 + PARAM_EPSILON;
+    if !range.contains(&t) || !range.contains(&u) {
+        return Vec::new();
+    }
+
+    // Snap to the ends of the edges so that both splits share the same point.
+    let end = |edge: Edge, t: f32| {
+        if t <= PARAM_EPSILON {
+            Some(edge.from)
+        } else if t >= 1.0 - PARAM_EPSILON {
+            Some(edge.to)
+        } else {
+            None
+        }
+    };
+    match (end(a, t), end(b, u)) {
+        (None, None) => {
+            let point = a.from + da * t;
+            vec![Meeting::Split(0, t, point), Meeting::Split(1, u, point)]
+        }
+        (Some(point), None) => vec![Meeting::Split(1, u, point)],
+        (None, Some(point)) => vec![Meeting::Split(0, t, point)],
+        (Some(end_a), Some(end_b)) if key(end_a) != key(end_b) => {
+            vec![Meeting::Merge(end_a, end_b)]
+        }
+        (Some(_), Some(_)) => Vec::new(),
+    }
+}
+
+/// Joins edges sharing their ends into closed rings.
+///
+/// Where several edges leave the same point, the one turning the most
+/// clockwise is followed, so that rings never cross themselves. Rings touching
+/// at a point are joined into a single ring going through it twice.
+fn link_edges(edges: &[(Vec2, Vec2)]) -> Vec<Polyline> {
+    let mut outgoing: HashMap<_, Vec<usize>> = HashMap::new();
+    for (i, edge) in edges.iter().enumerate() {
+        outgoing.entry(key(edge.0)).or_default().push(i);
+    }
+
+    let mut used = vec![false; edges.len()];
+    let mut rings = Vec::new();
+    let mut dropped = 0;
+    for start in 0..edges.len() {
+        if used[start] {
+            continue;
+        }
+        used[start] = true;
+        let mut points = vec![edges[start].0];
+        let mut previous = start;
+        let closed = loop {
+            let (from, current) = edges[previous];
+            if key(current) == key(edges[start].0) {
+                break true;
+            }
+            let candidates = outgoing.get(&key(current)).map_or(&[][..], Vec::as_slice);
+            let Some(next) = most_clockwise(edges, candidates, &used, current - from) else {
+                break false;
+            };
+            used[next] = true;
+            points.push(current);
+            previous = next;
+        };
+        if closed && points.len() > 2 {
+            rings.push(Polyline {
+                points,
+                closed: true,
+            });
+        } else if !closed {
+            dropped += points.len();
+        }
+    }
+    if dropped > 0 {
+        // Only happens if rounding errors made the outline inconsistent.
+        log::warn!("dropped {dropped} edges that do not form closed rings");
+    }
+    rings
+}
+
+/// Returns the unused edge among `candidates` that turns the most clockwise
+/// from the direction `incoming`.
+fn most_clockwise(
+    edges: &[(Vec2, Vec2)],
+    candidates: &[usize],
+    used: &[bool],
+    incoming: Vec2,
+) -> Option<usize> {
+    let turn_to = |i: usize| turn(incoming, edges[i].1 - edges[i].0);
+    candidates
+        .iter()
+        .copied()
+        .filter(|&i| !used[i])
+        .min_by(|&a, &b| {
+            turn_to(a)
+                .partial_cmp(&turn_to(b))
+                .unwrap_or(Ordering::Equal)
+        })
+}
+
+/// Returns the counter-clockwise angle from `incoming` to `outgoing`, between
+/// -π and π.
+fn turn(incoming: Vec2, outgoing: Vec2) -> f32 {
+    incoming.perp_dot(outgoing).atan2(incoming.dot(outgoing))
+}
+
+type Key = (u32, u32);
+
+/// Returns a hashable key identifying a point.
+fn key(point: Vec2) -> Key {
+    // Adding zero turns negative zeros into positive ones.
+    ((point.x + 0.0).to_bits(), (point.y + 0.0).to_bits())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::{PI, TAU};
+
+    use bevy::math::{Rect, Vec2};
+    use lyon_tessellation::FillOptions;
+
+    use super::BooleanOp;
+    use crate::{
+        entity::Path,
+        geometry::GeometryBuilder,
+        ops::{flatten, path_signed_area, TestRng},
+        shapes,
+    };
+
+    fn square(half_size: f32, angle: f32, center: Vec2) -> Path {
+        let rotation = Vec2::from_angle(angle);
+        let points = [(-1.0, -1.0), (1.0, -1.0), (1.0, 1.0), (-1.0, 1.0)]
+            .map(|(x, y)| center + rotation.rotate(Vec2::new(x, y) * half_size))
+            .to_vec();
+        GeometryBuilder::build_as(&shapes::Polygon {
+            points,
+            closed: true,
+        })
+    }
+
+    /// The signed area of the rings, which is the filled area for the
+    /// counter-clockwise outer rings and clockwise holes of the results.
+    fn area(path: &Path) -> f32 {
+        path_signed_area(&path.0, FillOptions::DEFAULT_TOLERANCE)
+    }
+
+    fn assert_identities(a: &Path, b: &Path) {
+        let op = |op| area(&a.boolean(b, op, &FillOptions::default()));
+        let (union, intersection) = (op(BooleanOp::Union), op(BooleanOp::Intersection));
+        let (difference, xor) = (op(BooleanOp::Difference), op(BooleanOp::Xor));
+        let (area_a, area_b) = (area(a), area(b));
+        let tolerance = 1e-4 * (area_a + area_b);
+        assert!(intersection >= -tolerance && intersection <= area_a.min(area_b) + tolerance);
+        assert!((union + intersection - area_a - area_b).abs() < tolerance);
+        assert!((difference + intersection - area_a).abs() < tolerance);
+        assert!((xor - (union - intersection)).abs() < tolerance);
+    }
+
+    #[test]
+    fn thin_overlap() {
+        let a = square(5.0, 0.0, Vec2::splat(5.0));
+        let b = square(5.0, 3.619_792, Vec2::new(9.097_219, 5.165_070_5));
+        assert_identities(&a, &b);
+    }
+
+    #[test]
+    fn random_rigid_transforms() {
+        let mut rng = TestRng::new(0x2545_f491);
+        // The results must not depend on the scale of the shapes.
+        for scale in [0.01, 1.0, 100.0] {
+            for _ in 0..100 {
+                let a = square(5.0 * scale, rng.range(0.0..TAU), Vec2::splat(5.0 * scale));
+                let center = Vec2::new(rng.range(-2.0..17.0), rng.range(-2.0..17.0)) * scale;
+                let b = square(rng.range(1.0..8.0) * scale, rng.range(0.0..TAU), center);
+                assert_identities(&a, &b);
+                let circle = GeometryBuilder::build_as(&shapes::Circle {
+                    radius: rng.range(1.0..8.0) * scale,
+                    center,
+                    ..shapes::Circle::default()
+                });
+                assert_identities(&a, &circle);
+            }
+        }
+    }
+
+    #[test]
+    fn nearly_coincident_edges() {
+        // Edges crossing at tiny angles next to their ends, which must still
+        // meet.
+        for angle in [1e-6, 1e-4, 1e-2] {
+            let a = square(5.0, 0.0, Vec2::splat(5.0));
+            let b = square(5.0, angle, Vec2::splat(5.0));
+            assert_identities(&a, &b);
+            assert!((area(&a.intersection(&b)) - 100.0).abs() < 100.0 * angle);
+        }
+    }
+
+    #[test]
+    fn small_scale() {
+        // The lens between two circles of radius r whose centers are r apart.
+        let radius = 0.01;
+        let circle = |center| {
+            GeometryBuilder::build_as(&shapes::Circle {
+                radius,
+                center,
+                ..shapes::Circle::default()
+            })
+        };
+        let (a, b) = (circle(Vec2::ZERO), circle(Vec2::new(radius, 0.0)));
+        let options = FillOptions::tolerance(radius * 1e-4);
+        let lens = area(&a.boolean(&b, BooleanOp::Intersection, &options));
+        let expected = radius * radius * (2.0 * PI / 3.0 - 3.0_f32.sqrt() / 2.0);
+        assert!((lens - expected).abs() < 1e-3 * expected);
+    }
+
+    #[test]
+    fn shared_edges() {
+        let a = GeometryBuilder::build_as(&shapes::Rectangle::new(Rect::new(0.0, 0.0, 2.0, 1.0)));
+        let b = GeometryBuilder::build_as(&shapes::Rectangle::new(Rect::new(1.0, 0.0, 3.0, 1.0)));
+        assert_identities(&a, &b);
+        assert!((area(&a.union(&b)) - 3.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn touching_corners() {
+        let a = GeometryBuilder::build_as(&shapes::Rectangle::new(Rect::new(0.0, 0.0, 1.0, 1.0)));
+        let b = GeometryBuilder::build_as(&shapes::Rectangle::new(Rect::new(1.0, 1.0, 2.0, 2.0)));
+        let union = a.union(&b);
+        assert!((area(&union) - 2.0).abs() < 1e-6);
+        assert!(flatten(&union.0, 0.1).iter().all(|ring| ring.closed));
+        assert!(area(&a.intersection(&b)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn hole() {
+        let outer = square(4.0, 0.0, Vec2::ZERO);
+        let inner = square(1.0, 0.3, Vec2::ZERO);
+        let frame = outer.difference(&inner);
+        assert!((area(&frame) - 60.0).abs() < 1e-4);
+        assert!((area(&frame.xor(&outer)) - 4.0).abs() < 1e-4);
+    }
+}
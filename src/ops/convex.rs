@@ -0,0 +1,207 @@
+//! Convex hulls and convex decompositions of paths.
+
+use std::collections::HashMap;
+
+use bevy::math::Vec2;
+use lyon_tessellation::{BuffersBuilder, FillOptions, FillTessellator, FillVertex, VertexBuffers};
+
+use super::{build_path, flatten, Polyline};
+use crate::{entity::Path, utils::ToVec2};
+
+impl Path {
+    /// Returns the smallest convex polygon containing this path, as a
+    /// counter-clockwise closed path. Curves are flattened first.
+    #[must_use]
+    pub fn convex_hull(&self) -> Self {
+        let mut points = flatten(&self.0, FillOptions::DEFAULT_TOLERANCE)
+            .into_iter()
+            .flat_map(|polyline| polyline.points)
+            .collect::<Vec<_>>();
+        points.sort_by(|a, b| a.x.total_cmp(&b.x).then(a.y.total_cmp(&b.y)));
+        points.dedup();
+        if points.len() < 3 {
+            return Self(build_path(&[Polyline {
+                points,
+                closed: true,
+            }]));
+        }
+
+        // Andrew's monotone chain: the lower hull from left to right, then the
+        // upper hull from right to left.
+        let turns_left = |hull: &[Vec2], point: Vec2| {
+            let (a, b) = (hull[hull.len() - 2], hull[hull.len() - 1]);
+            (b - a).perp_dot(point - b) > 0.0
+        };
+        let mut hull: Vec<Vec2> = Vec::with_capacity(points.len() + 1);
+        for &point in &points {
+            while hull.len() >= 2 && !turns_left(&hull, point) {
+                hull.pop();
+            }
+            hull.push(point);
+        }
+        let lower_len = hull.len();
+        for &point in points.iter().rev().skip(1) {
+            while hull.len() > lower_len && !turns_left(&hull, point) {
+                hull.pop();
+            }
+            hull.push(point);
+        }
+        hull.pop();
+
+        Self(build_path(&[Polyline {
+            points: hull,
+            closed: true,
+        }]))
+    }
+
+    /// Splits the region filled with `options` into convex polygons, each as
+    /// a list of counter-clockwise points.
+    ///
+    /// The region is triangulated, then neighbouring pieces are merged as long
+    /// as they stay convex, which gives at most four times the minimum number
+    /// of pieces.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_prototype_lyon::prelude::*;
+    /// #
+    /// let l_shape = GeometryBuilder::build_as(&shapes::Polygon {
+    ///     points: vec![
+    ///         Vec2::new(0.0, 0.0),
+    ///         Vec2::new(20.0, 0.0),
+    ///         Vec2::new(20.0, 10.0),
+    ///         Vec2::new(10.0, 10.0),
+    ///         Vec2::new(10.0, 20.0),
+    ///         Vec2::new(0.0, 20.0),
+    ///     ],
+    ///     closed: true,
+    /// });
+    ///
+    /// for piece in l_shape.convex_decomposition(&FillOptions::default()) {
+    ///     // Create a convex collider from the points of `piece`.
+    /// #   let n = piece.len();
+    /// #   assert!((0..n).all(|i| {
+    /// #       (piece[(i + 1) % n] - piece[i]).perp_dot(piece[(i + 2) % n] - piece[(i + 1) % n]) >= 0.0
+    /// #   }));
+    /// }
+    /// ```
+    #[must_use]
+    pub fn convex_decomposition(&self, options: &FillOptions) -> Vec<Vec<Vec2>> {
+        let mut buffers: VertexBuffers<Vec2, u32> = VertexBuffers::new();
+        let result = FillTessellator::new().tessellate_path(
+            &self.0,
+            options,
+            &mut BuffersBuilder::new(&mut buffers, |vertex: FillVertex| {
+                vertex.position().to_vec2()
+            }),
+        );
+        if result.is_err() {
+            return Vec::new();
+        }
+
+        // Vertices at the same position are merged so that neighbouring
+        // triangles share their edges.
+        let mut ids = HashMap::new();
+        let canonical = buffers
+            .vertices
+            .iter()
+            .enumerate()
+            .map(|(i, v)| *ids.entry((v.x.to_bits(), v.y.to_bits())).or_insert(i))
+            .collect::<Vec<_>>();
+        let triangles = buffers
+            .indices
+            .chunks_exact(3)
+            .filter_map(|triangle| {
+                let mut ids = [0, 1, 2].map(|i| canonical[triangle[i] as usize]);
+                let [a, b, c] = ids.map(|i| buffers.vertices[i]);
+                let area = (b - a).perp_dot(c - a);
+                if area.abs() <= f32::EPSILON {
+                    return None;
+                }
+                if area < 0.0 {
+                    ids.swap(1, 2);
+                }
+                Some(ids.to_vec())
+            })
+            .collect();
+
+        merge_convex(triangles, &buffers.vertices)
+            .into_iter()
+            .map(|polygon| polygon.into_iter().map(|i| buffers.vertices[i]).collect())
+            .collect()
+    }
+}
+
+/// Merges counter-clockwise polygons sharing an edge while the result stays
+/// convex (Hertel-Mehlhorn).
+fn merge_convex(mut polygons: Vec<Vec<usize>>, vertices: &[Vec2]) -> Vec<Vec<usize>> {
+    let mut edges = HashMap::new();
+    for (p, polygon) in polygons.iter().enumerate() {
+        for (a, b) in polygon_edges(polygon) {
+            edges.insert((a, b), p);
+        }
+    }
+    let mut diagonals = edges
+        .keys()
+        .filter(|&&(a, b)| a < b && edges.contains_key(&(b, a)))
+        .copied()
+        .collect::<Vec<_>>();
+    diagonals.sort_unstable();
+
+    let mut alive = vec![true; polygons.len()];
+    for (a, b) in diagonals {
+        let (p, q) = (edges[&(a, b)], edges[&(b, a)]);
+        if p == q {
+            continue;
+        }
+        // `p` goes from `b` around to `a`, then `q` from `a` around to `b`.
+        let mut merged = rotated(&polygons[p], b);
+        merged.extend(&rotated(&polygons[q], a)[1..polygons[q].len() - 1]);
+        if !is_convex_at(
+            &merged,
+            vertices,
+            merged.len() - 1 - (polygons[q].len() - 2),
+        ) || !is_convex_at(&merged, vertices, 0)
+        {
+            continue;
+        }
+        for &(c, d) in &polygon_edges(&polygons[q]) {
+            edges.insert((c, d), p);
+        }
+        edges.remove(&(a, b));
+        edges.remove(&(b, a));
+        polygons[p] = merged;
+        alive[q] = false;
+    }
+
+    polygons
+        .into_iter()
+        .zip(alive)
+        .filter_map(|(polygon, alive)| alive.then_some(polygon))
+        .collect()
+}
+
+fn polygon_edges(polygon: &[usize]) -> Vec<(usize, usize)> {
+    let n = polygon.len();
+    (0..n).map(|i| (polygon[i], polygon[(i + 1) % n])).collect()
+}
+
+/// Returns the polygon starting from the given vertex.
+fn rotated(polygon: &[usize], first: usize) -> Vec<usize> {
+    let start = polygon.iter().position(|&i| i == first).unwrap_or(0);
+    let mut rotated = polygon.to_vec();
+    rotated.rotate_left(start);
+    rotated
+}
+
+/// Returns whether the counter-clockwise polygon does not turn clockwise at
+/// its `i`-th vertex.
+fn is_convex_at(polygon: &[usize], vertices: &[Vec2], i: usize) -> bool {
+    let n = polygon.len();
+    let prev = vertices[polygon[(i + n - 1) % n]];
+    let current = vertices[polygon[i]];
+    let next = vertices[polygon[(i + 1) % n]];
+    (current - prev).perp_dot(next - current) >= -f32::EPSILON
+}
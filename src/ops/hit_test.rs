@@ -0,0 +1,282 @@
+//! Hit testing of paths and shapes.
+
+use bevy::{
+    ecs::query::QueryData,
+    math::{Rect, Vec2},
+    transform::components::GlobalTransform,
+};
+use lyon_algorithms::hit_test::hit_test_path;
+use lyon_tessellation::{path::Path as LyonPath, FillOptions, FillRule};
+
+use super::{flatten, markers::marker_path, offset::aligned_stroke_path, segment_distance};
+use crate::{
+    draw::{Fill, PathTrim, ShapeAnchor, Stroke, StrokeAlignment},
+    entity::Path,
+    utils::ToPoint,
+};
+
+impl Path {
+    /// Returns whether `point` is inside the region filled with the given fill
+    /// rule. Curves are flattened with `tolerance`.
+    #[must_use]
+    pub fn contains_point(&self, point: Vec2, fill_rule: FillRule, tolerance: f32) -> bool {
+        hit_test_path(&point.to_point(), self.0.iter(), fill_rule, tolerance)
+    }
+
+    /// Returns the distance between `point` and the closest point of this
+    /// path, including the closing segments of closed sub-paths, or infinity
+    /// if the path is empty. Curves are flattened with `tolerance`.
+    #[must_use]
+    pub fn distance_to_point(&self, point: Vec2, tolerance: f32) -> f32 {
+        distance(&self.0, point, tolerance)
+    }
+
+    /// Returns whether `point` is on the outline of this path drawn with
+    /// `stroke`, taking its line width, alignment and markers into account.
+    ///
+    /// Line caps and joins are approximated as round.
+    #[must_use]
+    pub fn stroke_contains_point(&self, point: Vec2, stroke: &Stroke) -> bool {
+        StrokeGeometry::new(&self.0, stroke).contains(point, stroke)
+    }
+}
+
+/// The paths drawn by a stroke: the path stroked centered, and the markers.
+struct StrokeGeometry {
+    line: LyonPath,
+    markers: Option<LyonPath>,
+}
+
+impl StrokeGeometry {
+    fn new(path: &LyonPath, stroke: &Stroke) -> Self {
+        let line = if stroke.alignment == StrokeAlignment::Center {
+            path.clone()
+        } else {
+            aligned_stroke_path(path, &stroke.options, stroke.alignment)
+        };
+        Self {
+            line,
+            markers: marker_path(path, stroke),
+        }
+    }
+
+    fn contains(&self, point: Vec2, stroke: &Stroke) -> bool {
+        let options = &stroke.options;
+        distance(&self.line, point, options.tolerance) <= 0.5 * options.line_width
+            || self.markers.as_ref().is_some_and(|markers| {
+                hit_test_path(
+                    &point.to_point(),
+                    markers.iter(),
+                    FillRule::NonZero,
+                    options.tolerance,
+                )
+            })
+    }
+
+    /// Returns a rectangle containing every point for which
+    /// [`contains`](Self::contains) returns `true`.
+    fn bounds(&self, stroke: &Stroke) -> Rect {
+        let line = Path(self.line.clone())
+            .aabb()
+            .inflate(0.5 * stroke.options.line_width);
+        self.markers
+            .as_ref()
+            .map_or(line, |markers| line.union(Path(markers.clone()).aabb()))
+    }
+}
+
+/// The components used to test whether points hit a shape as it is drawn.
+///
+/// The fill rule of the [`Fill`], the line width and alignment of the
+/// [`Stroke`], and the [`PathTrim`] and [`ShapeAnchor`] of the shape are taken
+/// into account. Shapes with neither fill nor stroke are filled, as when they
+/// are drawn.
+///
+/// # Example
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_prototype_lyon::{ops::ShapeHitTest, prelude::*};
+/// #
+/// fn select(
+///     camera: Single<(&Camera, &GlobalTransform)>,
+///     window: Single<&Window>,
+///     buttons: Res<ButtonInput<MouseButton>>,
+///     shapes: Query<(Entity, ShapeHitTest)>,
+/// ) {
+///     let (camera, camera_transform) = *camera;
+///     let Some(cursor) = window
+///         .cursor_position()
+///         .and_then(|cursor| camera.viewport_to_world_2d(camera_transform, cursor).ok())
+///     else {
+///         return;
+///     };
+///     if buttons.just_pressed(MouseButton::Left) {
+///         for (entity, shape) in &shapes {
+///             if shape.hit(cursor) {
+///                 info!("clicked {entity}");
+///             }
+///         }
+///     }
+/// }
+/// # bevy::ecs::system::assert_is_system(select);
+/// ```
+#[derive(QueryData)]
+pub struct ShapeHitTest {
+    /// The outline of the shape.
+    pub path: &'static Path,
+    /// The fill of the shape.
+    pub fill: Option<&'static Fill>,
+    /// The stroke of the shape.
+    pub stroke: Option<&'static Stroke>,
+    /// The part of the path that is drawn.
+    pub trim: Option<&'static PathTrim>,
+    /// Where the shape is drawn relative to its transform.
+    pub anchor: Option<&'static ShapeAnchor>,
+    /// The transform of the shape.
+    pub transform: &'static GlobalTransform,
+}
+
+impl ShapeHitTestItem<'_, '_> {
+    /// Returns whether `point`, in world space, is on the shape.
+    #[must_use]
+    pub fn hit(&self, point: Vec2) -> bool {
+        let local = self
+            .transform
+            .affine()
+            .inverse()
+            .transform_point3(point.extend(0.0));
+        self.hit_local(local.truncate())
+    }
+
+    /// Returns whether `point`, in the local space of the shape, is on the
+    /// shape.
+    #[must_use]
+    pub fn hit_local(&self, point: Vec2) -> bool {
+        ShapeGeometry::new(self).hit(point, self.fill_options(), self.stroke)
+    }
+
+    /// Returns the options of the fill of the shape, which is filled with the
+    /// default options if it has neither fill nor stroke.
+    pub(crate) fn fill_options(&self) -> Option<&FillOptions> {
+        match (self.fill, self.stroke) {
+            (None, None) => Some(&FillOptions::DEFAULT),
+            (fill, _) => fill.map(|fill| &fill.options),
+        }
+    }
+}
+
+/// The geometry of a shape as it is drawn, derived from its [`ShapeHitTest`]
+/// components, to test points against it.
+pub(crate) struct ShapeGeometry {
+    /// The translation applied by the [`ShapeAnchor`].
+    offset: Vec2,
+    /// The trimmed path.
+    path: Path,
+    stroke: Option<StrokeGeometry>,
+    /// Contains every point hitting the shape, before the translation.
+    bounds: Rect,
+}
+
+impl ShapeGeometry {
+    pub(crate) fn new(shape: &ShapeHitTestItem) -> Self {
+        let offset = shape
+            .anchor
+            .map_or(Vec2::ZERO, |anchor| anchor.offset(shape.path));
+        let path = match shape.trim {
+            Some(trim) => trim.apply(shape.path),
+            None => Path(shape.path.0.clone()),
+        };
+        let stroke = shape
+            .stroke
+            .map(|stroke| StrokeGeometry::new(&path.0, stroke));
+        let mut bounds = path.aabb();
+        if let (Some(geometry), Some(stroke)) = (&stroke, shape.stroke) {
+            bounds = bounds.union(geometry.bounds(stroke));
+        }
+        Self {
+            offset,
+            path,
+            stroke,
+            bounds,
+        }
+    }
+
+    /// Returns whether `point`, in the local space of the shape, is on the
+    /// shape filled with `fill` or drawn with `stroke`.
+    ///
+    /// `stroke` must be the stroke the geometry was built with, or `None` to
+    /// ignore it.
+    pub(crate) fn hit(
+        &self,
+        point: Vec2,
+        fill: Option<&FillOptions>,
+        stroke: Option<&Stroke>,
+    ) -> bool {
+        let point = point - self.offset;
+        if !self.bounds.contains(point) {
+            return false;
+        }
+        fill.is_some_and(|fill| {
+            self.path
+                .contains_point(point, fill.fill_rule, fill.tolerance)
+        }) || self
+            .stroke
+            .as_ref()
+            .zip(stroke)
+            .is_some_and(|(geometry, stroke)| geometry.contains(point, stroke))
+    }
+}
+
+fn distance(path: &LyonPath, point: Vec2, tolerance: f32) -> f32 {
+    flatten(path, tolerance)
+        .iter()
+        .flat_map(|polyline| {
+            let n = polyline.points.len();
+            let segments = if polyline.closed || n == 1 { n } else { n - 1 };
+            (0..segments).map(move |i| {
+                segment_distance(point, polyline.points[i], polyline.points[(i + 1) % n])
+            })
+        })
+        .fold(f32::INFINITY, f32::min)
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{
+        color::Color, ecs::world::World, math::Vec2, transform::components::GlobalTransform,
+    };
+
+    use super::ShapeHitTest;
+    use crate::{
+        draw::{Marker, Stroke},
+        geometry::GeometryBuilder,
+        shapes,
+    };
+
+    #[test]
+    fn stroke_and_markers_outside_the_path() {
+        let arrowhead = shapes::Polygon {
+            points: vec![Vec2::ZERO, Vec2::new(-3.0, 1.5), Vec2::new(-3.0, -1.5)],
+            closed: true,
+        };
+        let mut world = World::new();
+        let entity = world
+            .spawn((
+                GeometryBuilder::build_as(&shapes::Line(Vec2::ZERO, Vec2::new(10.0, 0.0))),
+                Stroke::new(Color::BLACK, 2.0).with_end_marker(Marker::new(&arrowhead)),
+                GlobalTransform::default(),
+            ))
+            .id();
+        let mut query = world.query::<ShapeHitTest>();
+        let shape = query.get(&world, entity).unwrap();
+
+        // The path itself has no height.
+        assert!(shape.hit_local(Vec2::new(2.0, 0.9)));
+        assert!(!shape.hit_local(Vec2::new(2.0, 1.1)));
+        // The arrowhead spans 6 line widths back from the end, and 3 across.
+        assert!(shape.hit_local(Vec2::new(5.0, 2.4)));
+        assert!(!shape.hit_local(Vec2::new(5.0, 2.6)));
+        assert!(!shape.hit_local(Vec2::new(10.5, 1.0)));
+    }
+}
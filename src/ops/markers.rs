@@ -0,0 +1,263 @@
+//! Placement of stroke markers along a path.
+
+use bevy::math::Vec2;
+use lyon_tessellation::{
+    geom::Angle,
+    math::Transform,
+    path::{Path as LyonPath, PathEvent},
+};
+
+use crate::{
+    draw::{Marker, Stroke},
+    utils::{ToVec2, ToVector},
+};
+
+/// A path vertex with the directions of the segments around it.
+struct Vertex {
+    position: Vec2,
+    incoming: Option<Vec2>,
+    outgoing: Option<Vec2>,
+}
+
+/// Returns the outlines of every marker of `stroke` placed along `path`, or
+/// `None` if the stroke has no markers.
+pub(crate) fn marker_path(path: &LyonPath, stroke: &Stroke) -> Option<LyonPath> {
+    if stroke.start_marker.is_none() && stroke.mid_marker.is_none() && stroke.end_marker.is_none() {
+        return None;
+    }
+
+    let line_width = stroke.options.line_width;
+    let mut b = LyonPath::builder();
+    let mut place = |marker: &Option<Marker>, position: Vec2, direction: Option<Vec2>| {
+        let (Some(marker), Some(direction)) = (marker, direction) else {
+            return;
+        };
+        let scale = if marker.scale_with_line_width {
+            line_width
+        } else {
+            1.0
+        };
+        let transform = Transform::scale(scale, scale)
+            .then_rotate(Angle::radians(direction.to_angle()))
+            .then_translate(position.to_vector());
+        let placed = marker.path.clone().transformed(&transform);
+        b.extend_from_paths(&[placed.as_slice()]);
+    };
+
+    for (vertices, closed) in sub_paths(path) {
+        let Some((first, last)) = vertices.first().zip(vertices.last()) else {
+            continue;
+        };
+        if closed {
+            for vertex in &vertices {
+                place(&stroke.mid_marker, vertex.position, vertex.bisector());
+            }
+            continue;
+        }
+        place(
+            &stroke.start_marker,
+            first.position,
+            first.outgoing.map(|d| -d),
+        );
+        place(&stroke.end_marker, last.position, last.incoming);
+        if vertices.len() > 2 {
+            for vertex in &vertices[1..vertices.len() - 1] {
+                place(&stroke.mid_marker, vertex.position, vertex.bisector());
+            }
+        }
+    }
+
+    Some(b.build())
+}
+
+impl Vertex {
+    /// Returns the direction halfway between the incoming and outgoing
+    /// directions.
+    fn bisector(&self) -> Option<Vec2> {
+        match (self.incoming, self.outgoing) {
+            (Some(incoming), Some(outgoing)) => Some(
+                (incoming.normalize() + outgoing.normalize())
+                    .try_normalize()
+                    .unwrap_or(outgoing),
+            ),
+            (incoming, outgoing) => incoming.or(outgoing),
+        }
+    }
+}
+
+/// Splits `path` into its sub-paths, each as a list of vertices and whether it
+/// is closed.
+fn sub_paths(path: &LyonPath) -> Vec<(Vec<Vertex>, bool)> {
+    let mut sub_paths = Vec::new();
+    let mut vertices: Vec<Vertex> = Vec::new();
+    for event in path {
+        let (to, start, end) = match event {
+            PathEvent::Begin { at } => {
+                vertices.push(Vertex {
+                    position: at.to_vec2(),
+                    incoming: None,
+                    outgoing: None,
+                });
+                continue;
+            }
+            PathEvent::End { last, first, close } => {
+                if close {
+                    let closing = (first - last).to_vec2();
+                    if closing.length_squared() > f32::EPSILON {
+                        if let Some(vertex) = vertices.last_mut() {
+                            vertex.outgoing = Some(closing);
+                        }
+                        vertices[0].incoming = Some(closing);
+                    } else if vertices.len() > 1 {
+                        // The last vertex coincides with the first one.
+                        let duplicate = vertices.pop().unwrap();
+                        vertices[0].incoming = duplicate.incoming;
+                    }
+                }
+                sub_paths.push((std::mem::take(&mut vertices), close));
+                continue;
+            }
+            segment => segment_directions(segment),
+        };
+        if start.length_squared() <= f32::EPSILON {
+            // Skip degenerate segments, they have no direction.
+            continue;
+        }
+        if let Some(vertex) = vertices.last_mut() {
+            vertex.outgoing = Some(start);
+        }
+        vertices.push(Vertex {
+            position: to,
+            incoming: Some(end),
+            outgoing: None,
+        });
+    }
+    sub_paths
+}
+
+/// Returns the end point of a segment with its directions at both ends.
+fn segment_directions(segment: PathEvent) -> (Vec2, Vec2, Vec2) {
+    match segment {
+        PathEvent::Line { from, to } => {
+            let d = (to - from).to_vec2();
+            (to.to_vec2(), d, d)
+        }
+        PathEvent::Quadratic { from, ctrl, to } => {
+            let (from, ctrl, to) = (from.to_vec2(), ctrl.to_vec2(), to.to_vec2());
+            (
+                to,
+                first_direction(&[ctrl - from, to - from]),
+                first_direction(&[to - ctrl, to - from]),
+            )
+        }
+        PathEvent::Cubic {
+            from,
+            ctrl1,
+            ctrl2,
+            to,
+        } => {
+            let (from, ctrl1, ctrl2, to) = (
+                from.to_vec2(),
+                ctrl1.to_vec2(),
+                ctrl2.to_vec2(),
+                to.to_vec2(),
+            );
+            (
+                to,
+                first_direction(&[ctrl1 - from, ctrl2 - from, to - from]),
+                first_direction(&[to - ctrl2, to - ctrl1, to - from]),
+            )
+        }
+        PathEvent::Begin { .. } | PathEvent::End { .. } => unreachable!(),
+    }
+}
+
+/// Returns the first non-degenerate direction among `candidates`.
+fn first_direction(candidates: &[Vec2]) -> Vec2 {
+    candidates
+        .iter()
+        .copied()
+        .find(|d| d.length_squared() > f32::EPSILON)
+        .unwrap_or(Vec2::ZERO)
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{color::Color, math::Vec2};
+    use lyon_tessellation::path::{Path as LyonPath, PathEvent};
+
+    use super::marker_path;
+    use crate::{
+        draw::{Marker, Stroke},
+        geometry::GeometryBuilder,
+        shapes,
+        utils::ToVec2,
+    };
+
+    /// Returns the tip and the direction of every arrowhead of `path`, from
+    /// the middle of its base to its tip.
+    fn arrowheads(path: &LyonPath) -> Vec<(Vec2, Vec2)> {
+        let mut arrowheads = Vec::new();
+        let mut points = Vec::new();
+        for event in path {
+            match event {
+                PathEvent::Begin { at } => points.push(at.to_vec2()),
+                PathEvent::Line { to, .. } => points.push(to.to_vec2()),
+                PathEvent::End { .. } => {
+                    let [tip, left, right] = points[..] else {
+                        panic!("arrowheads are triangles, got {points:?}");
+                    };
+                    arrowheads.push((tip, tip - (left + right) * 0.5));
+                    points.clear();
+                }
+                PathEvent::Quadratic { .. } | PathEvent::Cubic { .. } => unreachable!(),
+            }
+        }
+        arrowheads
+    }
+
+    #[test]
+    fn arrowheads_at_the_ends_and_corner() {
+        let arrowhead = Marker::new(&shapes::Polygon {
+            points: vec![Vec2::ZERO, Vec2::new(-3.0, 1.5), Vec2::new(-3.0, -1.5)],
+            closed: true,
+        });
+        let stroke = Stroke::new(Color::BLACK, 2.0)
+            .with_start_marker(arrowhead.clone())
+            .with_mid_marker(arrowhead.clone())
+            .with_end_marker(arrowhead);
+        let path = GeometryBuilder::build_as(&shapes::Polygon {
+            points: vec![Vec2::ZERO, Vec2::new(10.0, 0.0), Vec2::new(10.0, 10.0)],
+            closed: false,
+        });
+
+        let placed = arrowheads(&marker_path(&path.0, &stroke).unwrap());
+        // The arrowheads are 3 units long, scaled by the line width. The start
+        // one points away from the path, the end one along it, and the mid one
+        // along the bisector of the corner.
+        let expected = [
+            (Vec2::ZERO, Vec2::new(-6.0, 0.0)),
+            (Vec2::new(10.0, 10.0), Vec2::new(0.0, 6.0)),
+            (Vec2::new(10.0, 0.0), Vec2::splat(6.0 / 2f32.sqrt())),
+        ];
+        assert_eq!(placed.len(), expected.len());
+        for ((tip, direction), (expected_tip, expected_direction)) in
+            placed.into_iter().zip(expected)
+        {
+            assert!(
+                tip.abs_diff_eq(expected_tip, 1e-4),
+                "{tip} != {expected_tip}"
+            );
+            assert!(
+                direction.abs_diff_eq(expected_direction, 1e-4),
+                "{direction} != {expected_direction}"
+            );
+        }
+    }
+
+    #[test]
+    fn no_markers() {
+        let path = GeometryBuilder::build_as(&shapes::Line(Vec2::ZERO, Vec2::X));
+        assert!(marker_path(&path.0, &Stroke::new(Color::BLACK, 2.0)).is_none());
+    }
+}
@@ -0,0 +1,228 @@
+//! Measurement and sampling of paths by distance.
+
+use std::ops::Range;
+
+use bevy::math::Vec2;
+use lyon_algorithms::measure::{PathMeasurements, SampleType};
+use lyon_tessellation::{
+    path::{path::Builder, Path as LyonPath},
+    FillOptions,
+};
+
+use crate::{entity::Path, utils::ToVec2};
+
+/// A point along a path.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PathPoint {
+    /// The position of the point.
+    pub position: Vec2,
+    /// The normalized direction of the path at the point, or zero if the path
+    /// has no direction there.
+    pub tangent: Vec2,
+}
+
+/// The measured length of every segment of a path, to sample it by distance
+/// along the path.
+///
+/// Measuring a path flattens its curves, so a `PathMeasure` should be kept
+/// around when the same path is sampled many times. Distances run through
+/// every sub-path in order, without counting the gaps between them.
+///
+/// # Example
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_prototype_lyon::prelude::*;
+/// #
+/// let road = GeometryBuilder::build_as(&shapes::Spline {
+///     points: vec![Vec2::ZERO, Vec2::new(100.0, 50.0), Vec2::new(200.0, 0.0)],
+///     ..default()
+/// });
+/// let measure = road.measure();
+///
+/// // Place a tree every 20 pixels along the road, facing its direction.
+/// let trees = measure
+///     .sample_every(20.0)
+///     .into_iter()
+///     .map(|point| {
+///         Transform::from_translation(point.position.extend(0.0))
+///             .with_rotation(Quat::from_rotation_z(point.tangent.to_angle()))
+///     })
+///     .collect::<Vec<_>>();
+/// # assert_eq!(trees.len(), (measure.length() / 20.0) as usize + 1);
+/// ```
+pub struct PathMeasure {
+    path: LyonPath,
+    measurements: PathMeasurements,
+}
+
+impl PathMeasure {
+    /// Measures `path`, flattening its curves with the given tolerance.
+    #[must_use]
+    pub fn new(path: &Path, tolerance: f32) -> Self {
+        Self {
+            path: path.0.clone(),
+            measurements: PathMeasurements::from_path(&path.0, tolerance),
+        }
+    }
+
+    /// Returns the length of the path.
+    #[must_use]
+    pub fn length(&self) -> f32 {
+        self.measurements.length()
+    }
+
+    /// Returns the point at `distance` along the path, clamped between its
+    /// start and its end, or `None` if the path is empty.
+    #[must_use]
+    pub fn sample(&self, distance: f32) -> Option<PathPoint> {
+        self.sample_distances(std::iter::once(distance)).pop()
+    }
+
+    /// Returns the position at `distance` along the path. See
+    /// [`sample`](Self::sample).
+    #[must_use]
+    pub fn position_at(&self, distance: f32) -> Option<Vec2> {
+        self.sample(distance).map(|point| point.position)
+    }
+
+    /// Returns the direction of the path at `distance` along it. See
+    /// [`sample`](Self::sample).
+    #[must_use]
+    pub fn tangent_at(&self, distance: f32) -> Option<Vec2> {
+        self.sample(distance).map(|point| point.tangent)
+    }
+
+    /// Returns `count` points evenly spaced along the path, including its
+    /// start and its end.
+    #[must_use]
+    pub fn resample(&self, count: usize) -> Vec<PathPoint> {
+        let step = if count > 1 {
+            self.length() / (count - 1) as f32
+        } else {
+            0.0
+        };
+        self.sample_distances((0..count).map(|i| i as f32 * step))
+    }
+
+    /// Returns points separated by `spacing` along the path, starting at its
+    /// start. The end of the path is only included if the length of the path
+    /// is a multiple of `spacing`.
+    ///
+    /// The number of points is the length divided by `spacing`, rounded down,
+    /// plus one, so it grows without bound as `spacing` gets smaller. Use
+    /// [`resample`](Self::resample) for a fixed number of points.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `spacing` is not positive, including NaN, or if the number
+    /// of points does not fit in a `usize`.
+    #[must_use]
+    pub fn sample_every(&self, spacing: f32) -> Vec<PathPoint> {
+        assert!(spacing > 0.0, "the spacing must be positive");
+        let intervals = (self.length() / spacing).floor();
+        assert!(
+            intervals < usize::MAX as f32,
+            "the spacing is too small for the length of the path"
+        );
+        #[allow(clippy::cast_sign_loss)] // Both the length and the spacing are positive.
+        let count = intervals as usize + 1;
+        self.sample_distances((0..count).map(|i| i as f32 * spacing))
+    }
+
+    /// Returns the part of the path between two distances along it, clamped
+    /// between its start and its end.
+    #[must_use]
+    pub fn split(&self, range: Range<f32>) -> Path {
+        let mut b = Builder::new();
+        self.split_into(range, &mut b);
+        Path(b.build())
+    }
+
+    /// Writes the part of the path between two distances along it into `b`.
+    pub(crate) fn split_into(&self, range: Range<f32>, b: &mut Builder) {
+        // Lyon panics when splitting an empty path.
+        if self.length() > 0.0 {
+            self.measurements
+                .create_sampler(&self.path, SampleType::Distance)
+                .split_range(range, b);
+        }
+    }
+
+    fn sample_distances(&self, distances: impl Iterator<Item = f32>) -> Vec<PathPoint> {
+        // Increasing distances are sampled faster with a single sampler.
+        let mut sampler = self
+            .measurements
+            .create_sampler(&self.path, SampleType::Distance);
+        distances
+            .map(|distance| {
+                let sample = sampler.sample(distance);
+                let tangent = sample.tangent().to_vec2();
+                PathPoint {
+                    position: sample.position().to_vec2(),
+                    tangent: if tangent.is_nan() { Vec2::ZERO } else { tangent },
+                }
+            })
+            .filter(|point| !point.position.is_nan())
+            .collect()
+    }
+}
+
+impl Path {
+    /// Measures this path to sample it by distance, with the default
+    /// tolerance. See [`PathMeasure`].
+    #[must_use]
+    pub fn measure(&self) -> PathMeasure {
+        PathMeasure::new(self, FillOptions::DEFAULT_TOLERANCE)
+    }
+
+    /// Returns the part of this path between `start` and `end`, as fractions of
+    /// its length. See [`PathTrim`](crate::draw::PathTrim) to trim the path of
+    /// a shape while drawing it.
+    #[must_use]
+    pub fn trimmed(&self, start: f32, end: f32) -> Self {
+        if start <= 0.0 && end >= 1.0 {
+            return Self(self.0.clone());
+        }
+        let measure = self.measure();
+        let length = measure.length();
+        measure.split(start * length..end * length)
+    }
+
+    /// Returns the length of this path, adding the lengths of its sub-paths.
+    #[must_use]
+    pub fn length(&self) -> f32 {
+        self.measure().length()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::math::Vec2;
+
+    use crate::{entity::Path, geometry::GeometryBuilder, shapes};
+
+    fn line() -> Path {
+        GeometryBuilder::build_as(&shapes::Line(Vec2::ZERO, Vec2::new(10.0, 0.0)))
+    }
+
+    #[test]
+    fn sample_every() {
+        let measure = line().measure();
+        assert_eq!(measure.sample_every(3.0).len(), 4);
+        assert_eq!(measure.sample_every(2.5).len(), 5);
+        assert_eq!(measure.sample_every(f32::INFINITY).len(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "the spacing must be positive")]
+    fn sample_every_nan() {
+        let _ = line().measure().sample_every(f32::NAN);
+    }
+
+    #[test]
+    #[should_panic(expected = "the spacing is too small")]
+    fn sample_every_tiny_spacing() {
+        let _ = line().measure().sample_every(f32::MIN_POSITIVE);
+    }
+}
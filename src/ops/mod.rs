@@ -0,0 +1,195 @@
+//! Geometric operations on paths.
+
+pub(crate) mod boolean;
+mod convex;
+mod hit_test;
+pub(crate) mod markers;
+mod measure;
+mod morph;
+pub(crate) mod offset;
+mod region;
+mod simplify;
+
+pub use boolean::BooleanOp;
+#[cfg(feature = "picking")]
+pub(crate) use hit_test::ShapeGeometry;
+pub use hit_test::{ShapeHitTest, ShapeHitTestItem};
+pub use measure::{PathMeasure, PathPoint};
+pub use morph::PathMorph;
+pub use simplify::SimplifyMethod;
+
+use bevy::math::Vec2;
+use lyon_tessellation::path::{
+    iterator::PathIterator, path::Builder, Path as LyonPath, PathEvent, Polygon as LyonPolygon,
+};
+
+use crate::utils::{ToPoint, ToVec2};
+
+/// Points closer than this are merged when flattening a path.
+const MERGE_EPSILON: f32 = 1e-5;
+
+/// A sub-path approximated by straight line segments.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub(crate) struct Polyline {
+    pub points: Vec<Vec2>,
+    pub closed: bool,
+}
+
+impl Polyline {
+    /// Returns the signed area enclosed by the polyline, as if it was closed.
+    /// Counter-clockwise rings have a positive area.
+    pub fn signed_area(&self) -> f32 {
+        signed_area(&self.points)
+    }
+
+    /// Returns `true` if `point` is inside the polyline, as if it was closed,
+    /// using the even-odd rule.
+    pub fn contains(&self, point: Vec2) -> bool {
+        let n = self.points.len();
+        let mut inside = false;
+        for i in 0..n {
+            let a = self.points[i];
+            let b = self.points[(i + 1) % n];
+            if (a.y > point.y) != (b.y > point.y) {
+                let x = (b.x - a.x).mul_add((point.y - a.y) / (b.y - a.y), a.x);
+                if point.x < x {
+                    inside = !inside;
+                }
+            }
+        }
+        inside
+    }
+
+    /// Removes consecutive duplicate points, including the closing point of
+    /// closed polylines.
+    fn dedup(&mut self) {
+        self.points
+            .dedup_by(|a, b| a.distance_squared(*b) < MERGE_EPSILON * MERGE_EPSILON);
+        if self.closed && self.points.len() > 1 {
+            let (first, last) = (self.points[0], self.points[self.points.len() - 1]);
+            if first.distance_squared(last) < MERGE_EPSILON * MERGE_EPSILON {
+                self.points.pop();
+            }
+        }
+    }
+}
+
+/// Approximates every sub-path of `path` with a [`Polyline`].
+pub(crate) fn flatten(path: &LyonPath, tolerance: f32) -> Vec<Polyline> {
+    let mut polylines = Vec::new();
+    let mut current = Polyline::default();
+    for event in path.iter().flattened(tolerance) {
+        match event {
+            PathEvent::Begin { at } => {
+                current.points.push(at.to_vec2());
+            }
+            PathEvent::Line { to, .. } => current.points.push(to.to_vec2()),
+            PathEvent::End { close, .. } => {
+                current.closed = close;
+                current.dedup();
+                polylines.push(std::mem::take(&mut current));
+            }
+            // A flattened iterator only emits line segments.
+            PathEvent::Quadratic { .. } | PathEvent::Cubic { .. } => unreachable!(),
+        }
+    }
+    polylines
+}
+
+/// Splits `path` into one path per sub-path.
+pub(crate) fn split_sub_paths(path: &LyonPath) -> Vec<LyonPath> {
+    let mut sub_paths = Vec::new();
+    let mut b = LyonPath::builder();
+    for event in path {
+        b.path_event(event);
+        if let PathEvent::End { .. } = event {
+            sub_paths.push(std::mem::replace(&mut b, LyonPath::builder()).build());
+        }
+    }
+    sub_paths
+}
+
+/// Builds a lyon path made of the given polylines.
+pub(crate) fn build_path(polylines: &[Polyline]) -> LyonPath {
+    let mut b = Builder::new();
+    for polyline in polylines {
+        let points = polyline
+            .points
+            .iter()
+            .map(|p| p.to_point())
+            .collect::<Vec<_>>();
+        b.add_polygon(LyonPolygon {
+            points: &points,
+            closed: polyline.closed,
+        });
+    }
+    b.build()
+}
+
+/// For each polyline, returns whether the filled region lies on its left.
+///
+/// Closed polylines nested inside an odd number of other closed polylines are
+/// treated as holes. Open polylines enclose nothing and always yield `false`.
+pub(crate) fn interior_on_left(polylines: &[Polyline]) -> Vec<bool> {
+    polylines
+        .iter()
+        .enumerate()
+        .map(|(i, polyline)| {
+            if !polyline.closed || polyline.points.len() < 3 {
+                return false;
+            }
+            let depth = polylines
+                .iter()
+                .enumerate()
+                .filter(|&(j, other)| {
+                    j != i && other.closed && other.contains(polyline.points[0])
+                })
+                .count();
+            (polyline.signed_area() > 0.0) == (depth % 2 == 0)
+        })
+        .collect()
+}
+
+/// Returns the signed area enclosed by a ring of points, with the shoelace
+/// formula. Counter-clockwise rings have a positive area.
+pub(crate) fn signed_area(points: &[Vec2]) -> f32 {
+    let n = points.len();
+    (0..n)
+        .map(|i| points[i].perp_dot(points[(i + 1) % n]))
+        .sum::<f32>()
+        * 0.5
+}
+
+/// Returns the sum of the signed areas of the sub-paths of `path`, flattened
+/// with `tolerance`.
+pub(crate) fn path_signed_area(path: &LyonPath, tolerance: f32) -> f32 {
+    flatten(path, tolerance).iter().map(Polyline::signed_area).sum()
+}
+
+/// Returns the distance between `point` and the segment from `a` to `b`.
+pub(crate) fn segment_distance(point: Vec2, a: Vec2, b: Vec2) -> f32 {
+    let ab = b - a;
+    let t = ((point - a).dot(ab) / ab.length_squared()).clamp(0.0, 1.0);
+    if t.is_nan() {
+        return point.distance(a);
+    }
+    point.distance(a + ab * t)
+}
+
+/// A small linear congruential generator, which keeps the randomized tests
+/// reproducible.
+#[cfg(test)]
+pub(crate) struct TestRng(u32);
+
+#[cfg(test)]
+impl TestRng {
+    pub fn new(seed: u32) -> Self {
+        Self(seed)
+    }
+
+    /// Returns a number in `range`.
+    pub fn range(&mut self, range: std::ops::Range<f32>) -> f32 {
+        self.0 = self.0.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+        range.start + (range.end - range.start) * (self.0 >> 8) as f32 / (1 << 24) as f32
+    }
+}
@@ -0,0 +1,226 @@
+//! Interpolation between two paths.
+
+use bevy::math::Vec2;
+use lyon_tessellation::FillOptions;
+
+use super::{build_path, flatten, Polyline};
+use crate::entity::Path;
+
+/// An interpolation between two paths, made of any number of sub-paths and
+/// segments.
+///
+/// Both paths are flattened and their sub-paths are paired in order; missing
+/// sub-paths grow from or shrink to a point. Paired sub-paths are resampled
+/// so that every vertex of one has a counterpart on the other at the same
+/// fraction of their length, which keeps the corners of both paths sharp.
+/// Closed sub-paths are also given the same orientation and start from their
+/// closest vertices, to avoid twisting.
+///
+/// Creating a `PathMorph` does all the matching work, so it should be kept
+/// when animating between the same two paths.
+///
+/// # Example
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_prototype_lyon::prelude::*;
+/// # use bevy_prototype_lyon::ops::PathMorph;
+/// #
+/// let triangle = GeometryBuilder::build_as(&shapes::RegularPolygon {
+///     sides: 3,
+///     ..default()
+/// });
+/// let square = GeometryBuilder::build_as(&shapes::Rectangle::new(Rect::from_center_size(
+///     Vec2::ZERO,
+///     Vec2::splat(2.0),
+/// )));
+///
+/// let morph = PathMorph::new(&triangle, &square);
+/// let halfway = morph.at(0.5);
+///
+/// // The ends of the morph are the two paths.
+/// for (t, end) in [(0.0, &triangle), (1.0, &square)] {
+///     let path = morph.at(t);
+///     let area = end.area(FillRule::NonZero);
+///     assert!((path.area(FillRule::NonZero) - area).abs() < 1e-4 * area);
+///     assert!(path.aabb().min.abs_diff_eq(end.aabb().min, 1e-4));
+///     assert!(path.aabb().max.abs_diff_eq(end.aabb().max, 1e-4));
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct PathMorph {
+    pairs: Vec<(Polyline, Polyline)>,
+}
+
+impl PathMorph {
+    /// Matches the sub-paths and vertices of `from` and `to`.
+    #[must_use]
+    pub fn new(from: &Path, to: &Path) -> Self {
+        let mut from = flatten(&from.0, FillOptions::DEFAULT_TOLERANCE);
+        let mut to = flatten(&to.0, FillOptions::DEFAULT_TOLERANCE);
+        from.retain(|polyline| !polyline.points.is_empty());
+        to.retain(|polyline| !polyline.points.is_empty());
+
+        // Missing sub-paths are points at the center of their counterpart.
+        let missing_from = to.iter().skip(from.len()).map(collapsed).collect::<Vec<_>>();
+        let missing_to = from.iter().skip(to.len()).map(collapsed).collect::<Vec<_>>();
+        from.extend(missing_from);
+        to.extend(missing_to);
+
+        let pairs = from.into_iter().zip(to).map(|(a, b)| match_pair(a, b)).collect();
+        Self { pairs }
+    }
+
+    /// Returns the path at `t` between the two paths, where `0.0` gives the
+    /// first one and `1.0` the second one.
+    #[must_use]
+    pub fn at(&self, t: f32) -> Path {
+        let polylines = self
+            .pairs
+            .iter()
+            .map(|(from, to)| Polyline {
+                points: from
+                    .points
+                    .iter()
+                    .zip(&to.points)
+                    .map(|(a, b)| a.lerp(*b, t))
+                    .collect(),
+                closed: from.closed,
+            })
+            .collect::<Vec<_>>();
+        Path(build_path(&polylines))
+    }
+}
+
+fn collapsed(polyline: &Polyline) -> Polyline {
+    let center = polyline.points.iter().sum::<Vec2>() / polyline.points.len() as f32;
+    Polyline {
+        points: vec![center],
+        closed: polyline.closed,
+    }
+}
+
+/// Resamples two sub-paths so that they have as many points, at the same
+/// fractions of their lengths.
+fn match_pair(mut from: Polyline, mut to: Polyline) -> (Polyline, Polyline) {
+    let closed = from.closed && to.closed;
+    if closed && to.points.len() > 2 {
+        if (from.signed_area() > 0.0) != (to.signed_area() > 0.0) {
+            to.points.reverse();
+        }
+        // Start from the vertex closest to the start of the other sub-path,
+        // relative to their centers.
+        let offset = collapsed(&to).points[0] - collapsed(&from).points[0];
+        let start = from.points[0] + offset;
+        let first = (0..to.points.len())
+            .min_by(|&a, &b| {
+                let distance = |i: usize| to.points[i].distance_squared(start);
+                distance(a).total_cmp(&distance(b))
+            })
+            .unwrap_or(0);
+        to.points.rotate_left(first);
+    }
+    for polyline in [&mut from, &mut to] {
+        if polyline.closed {
+            // The closing segment is sampled like the others. Closed
+            // sub-paths paired with open ones are opened at their start.
+            polyline.points.push(polyline.points[0]);
+        }
+    }
+
+    let mut fractions = vertex_fractions(&from.points);
+    fractions.extend(vertex_fractions(&to.points));
+    fractions.sort_by(f32::total_cmp);
+    fractions.dedup_by(|a, b| (*a - *b).abs() < 1e-6);
+    if closed {
+        // The end point is the start point again.
+        fractions.pop();
+    }
+
+    let resample = |polyline: &Polyline| Polyline {
+        points: resample(&polyline.points, &fractions),
+        closed,
+    };
+    (resample(&from), resample(&to))
+}
+
+/// Returns the fraction of the length of the polyline at each vertex.
+fn vertex_fractions(points: &[Vec2]) -> Vec<f32> {
+    let distances = cumulative_lengths(points);
+    let length = distances[distances.len() - 1];
+    if length <= 0.0 {
+        return vec![0.0, 1.0];
+    }
+    distances.iter().map(|distance| distance / length).collect()
+}
+
+/// Returns the points at the given increasing fractions of the length of the
+/// polyline.
+fn resample(points: &[Vec2], fractions: &[f32]) -> Vec<Vec2> {
+    let distances = cumulative_lengths(points);
+    let length = distances[distances.len() - 1];
+    let mut segment = 0;
+    fractions
+        .iter()
+        .map(|fraction| {
+            let distance = fraction * length;
+            while segment + 2 < points.len() && distances[segment + 1] < distance {
+                segment += 1;
+            }
+            if points.len() == 1 {
+                return points[0];
+            }
+            let segment_length = distances[segment + 1] - distances[segment];
+            let t = if segment_length > 0.0 {
+                ((distance - distances[segment]) / segment_length).clamp(0.0, 1.0)
+            } else {
+                0.0
+            };
+            points[segment].lerp(points[segment + 1], t)
+        })
+        .collect()
+}
+
+/// Returns the distance along the polyline at each vertex.
+fn cumulative_lengths(points: &[Vec2]) -> Vec<f32> {
+    let mut distances = Vec::with_capacity(points.len());
+    let mut distance = 0.0;
+    distances.push(distance);
+    for segment in points.windows(2) {
+        distance += segment[0].distance(segment[1]);
+        distances.push(distance);
+    }
+    distances
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::math::{Rect, Vec2};
+    use lyon_tessellation::FillRule;
+
+    use super::PathMorph;
+    use crate::{entity::Path, geometry::GeometryBuilder, ops::flatten, shapes};
+
+    fn square(center: Vec2, size: f32) -> shapes::Rectangle {
+        shapes::Rectangle::new(Rect::from_center_size(center, Vec2::splat(size)))
+    }
+
+    #[test]
+    fn different_sub_path_counts() {
+        let one = GeometryBuilder::build_as(&square(Vec2::ZERO, 2.0));
+        let two = GeometryBuilder::build_as(&(
+            square(Vec2::ZERO, 2.0),
+            square(Vec2::new(10.0, 0.0), 2.0),
+        ));
+        // The missing square grows from its center, and is half grown halfway.
+        let check = |halfway: Path| {
+            assert_eq!(flatten(&halfway.0, 0.1).len(), 2);
+            assert!((halfway.area(FillRule::NonZero) - 5.0).abs() < 1e-4);
+            let bounds = halfway.aabb();
+            assert!(bounds.min.abs_diff_eq(Vec2::new(-1.0, -1.0), 1e-4));
+            assert!(bounds.max.abs_diff_eq(Vec2::new(10.5, 1.0), 1e-4));
+        };
+        check(PathMorph::new(&one, &two).at(0.5));
+        check(PathMorph::new(&two, &one).at(0.5));
+    }
+}
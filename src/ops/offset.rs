@@ -0,0 +1,349 @@
+//! Parallel curves of paths.
+
+use std::f32::consts::PI;
+
+use bevy::math::Vec2;
+use lyon_tessellation::{path::Path as LyonPath, FillOptions, LineJoin, StrokeOptions};
+
+use super::{boolean::resolve_rings, build_path, flatten, interior_on_left, Polyline};
+use crate::{draw::StrokeAlignment, entity::Path};
+
+/// Cosine threshold under which two consecutive segments are considered
+/// collinear or folded back onto each other.
+const ANGLE_EPSILON: f32 = 1e-4;
+
+/// Offsets every closed polyline away from the filled region by `distance`.
+///
+/// A negative `distance` moves the rings towards the filled region. The
+/// resulting rings have the filled region on their left and do not intersect
+/// themselves. Open polylines enclose nothing and are returned unchanged.
+pub(crate) fn offset_polylines(
+    polylines: &[Polyline],
+    distance: f32,
+    join: LineJoin,
+    miter_limit: f32,
+    tolerance: f32,
+) -> Vec<Polyline> {
+    let mut rings = Vec::new();
+    let mut open = Vec::new();
+    let mut convex = true;
+    for (polyline, interior_on_left) in polylines.iter().zip(interior_on_left(polylines)) {
+        if !polyline.closed || polyline.points.len() < 3 {
+            open.push(polyline.clone());
+            continue;
+        }
+        // Rings are oriented so that positive distances, which go to the
+        // right of the ring direction, move away from the filled region.
+        let mut points = polyline.points.clone();
+        if !interior_on_left {
+            points.reverse();
+        }
+        convex &= is_convex(&points);
+        rings.push(Polyline {
+            points: offset_ring(&points, distance, join, miter_limit, tolerance),
+            closed: true,
+        });
+    }
+
+    // Growing a single convex ring gives a convex ring, which needs no
+    // cleanup. Otherwise, rings folding over themselves, around corners or
+    // edges shorter than the distance, wind around the parts to remove zero
+    // or negative times. Rings that do not cross skip most of that work.
+    let mut result = if distance > 0.0 && rings.len() == 1 && convex {
+        rings
+    } else {
+        resolve_rings(rings, |winding| winding > 0)
+    };
+    result.extend(open);
+    result
+}
+
+/// Returns `true` if the ring only turns left and goes around once.
+fn is_convex(points: &[Vec2]) -> bool {
+    let n = points.len();
+    let mut turning = 0.0;
+    for i in 0..n {
+        let t0 = points[(i + 1) % n] - points[i];
+        let t1 = points[(i + 2) % n] - points[(i + 1) % n];
+        let turn = t0.perp_dot(t1).atan2(t0.dot(t1));
+        if turn < 0.0 {
+            return false;
+        }
+        turning += turn;
+    }
+    // Going around once turns by 2π, and twice by 4π.
+    turning < 3.0 * PI
+}
+
+impl Path {
+    /// Returns the outline of the filled region of this path grown by
+    /// `distance`, or shrunk if `distance` is negative.
+    ///
+    /// Open sub-paths are considered closed, as when filling, and nested
+    /// sub-paths are holes. `join` shapes the corners moving away from the
+    /// filled region, with the default miter limit of [`StrokeOptions`]. Parts
+    /// of the region thinner than twice a negative `distance` disappear.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_prototype_lyon::prelude::*;
+    /// #
+    /// let room =
+    ///     GeometryBuilder::build_as(&shapes::Rectangle::new(Rect::new(-50.0, -50.0, 50.0, 50.0)));
+    /// let border = room.offset(10.0, LineJoin::Miter);
+    /// assert!((border.area(FillRule::NonZero) - 120.0 * 120.0).abs() < 0.1);
+    /// assert!(border.contains_point(Vec2::new(59.0, 59.0), FillRule::NonZero, 0.1));
+    /// assert!(!border.contains_point(Vec2::new(61.0, 0.0), FillRule::NonZero, 0.1));
+    ///
+    /// let arena = GeometryBuilder::build_as(&shapes::Circle {
+    ///     radius: 50.0,
+    ///     ..default()
+    /// });
+    /// let walkable = arena.offset(-20.0, LineJoin::Round);
+    /// let expected = std::f32::consts::PI * 30.0 * 30.0;
+    /// assert!((walkable.area(FillRule::NonZero) - expected).abs() < 0.01 * expected);
+    /// assert!(walkable.contains_point(Vec2::new(0.0, 29.0), FillRule::NonZero, 0.1));
+    /// assert!(!walkable.contains_point(Vec2::new(0.0, 31.0), FillRule::NonZero, 0.1));
+    /// ```
+    #[must_use]
+    pub fn offset(&self, distance: f32, join: LineJoin) -> Self {
+        let tolerance = FillOptions::DEFAULT_TOLERANCE;
+        let mut polylines = flatten(&self.0, tolerance);
+        for polyline in &mut polylines {
+            polyline.closed = true;
+        }
+        Self(build_path(&offset_polylines(
+            &polylines,
+            distance,
+            join,
+            StrokeOptions::DEFAULT_MITER_LIMIT,
+            tolerance,
+        )))
+    }
+}
+
+/// Returns the path to stroke, centered, so that the stroke of `path` ends up
+/// with the given `alignment`.
+pub(crate) fn aligned_stroke_path(
+    path: &LyonPath,
+    options: &StrokeOptions,
+    alignment: StrokeAlignment,
+) -> LyonPath {
+    let distance = match alignment {
+        StrokeAlignment::Center => return path.clone(),
+        StrokeAlignment::Inside => -0.5 * options.line_width,
+        StrokeAlignment::Outside => 0.5 * options.line_width,
+    };
+    let polylines = flatten(path, options.tolerance);
+    build_path(&offset_polylines(
+        &polylines,
+        distance,
+        options.line_join,
+        options.miter_limit,
+        options.tolerance,
+    ))
+}
+
+/// Offsets a closed ring to the right of its direction by `distance`.
+fn offset_ring(
+    points: &[Vec2],
+    distance: f32,
+    join: LineJoin,
+    miter_limit: f32,
+    tolerance: f32,
+) -> Vec<Vec2> {
+    let n = points.len();
+    let mut out = Vec::with_capacity(n);
+    for i in 0..n {
+        let prev = points[(i + n - 1) % n];
+        let p = points[i];
+        let next = points[(i + 1) % n];
+        let t0 = (p - prev).normalize_or_zero();
+        let t1 = (next - p).normalize_or_zero();
+        add_join(
+            &mut out,
+            Corner { p, t0, t1 },
+            distance,
+            join,
+            miter_limit,
+            tolerance,
+        );
+    }
+    out
+}
+
+/// A vertex of a ring with the directions of its incoming and outgoing edges.
+struct Corner {
+    p: Vec2,
+    t0: Vec2,
+    t1: Vec2,
+}
+
+fn add_join(
+    out: &mut Vec<Vec2>,
+    corner: Corner,
+    distance: f32,
+    join: LineJoin,
+    miter_limit: f32,
+    tolerance: f32,
+) {
+    let Corner { p, t0, t1 } = corner;
+    // Right-hand normals of both edges, scaled by the offset distance.
+    let m0 = Vec2::new(t0.y, -t0.x) * distance;
+    let m1 = Vec2::new(t1.y, -t1.x) * distance;
+    let cos = t0.dot(t1);
+
+    if cos > 1.0 - ANGLE_EPSILON {
+        out.push(p + m0);
+        return;
+    }
+    if cos < ANGLE_EPSILON - 1.0 {
+        out.extend([p + m0, p + m1]);
+        return;
+    }
+
+    // The offset side of the corner is convex when the path turns away from it.
+    let convex = t0.perp_dot(t1) * distance > 0.0;
+    if !convex {
+        // Going through the vertex makes the overlapping parts of the offset
+        // edges, and rings turned inside out, wind negatively around the
+        // region to remove.
+        out.extend([p + m0, p, p + m1]);
+        return;
+    }
+
+    let miter = p + (m0 + m1) / (1.0 + cos);
+
+    match join {
+        LineJoin::Round => add_round_join(out, p, m0, m1, distance.abs(), tolerance),
+        LineJoin::Bevel => out.extend([p + m0, p + m1]),
+        LineJoin::Miter | LineJoin::MiterClip => {
+            // Ratio between the miter length and the offset distance.
+            let ratio = (2.0 / (1.0 + cos)).sqrt();
+            if ratio <= miter_limit {
+                out.push(miter);
+            } else if join == LineJoin::MiterClip {
+                let bisector = (m0 + m1).normalize();
+                let limit = miter_limit * distance.abs();
+                let s0 = (limit - m0.dot(bisector)) / t0.dot(bisector);
+                let s1 = (limit - m1.dot(bisector)) / t1.dot(bisector);
+                out.extend([p + m0 + t0 * s0, p + m1 + t1 * s1]);
+            } else {
+                out.extend([p + m0, p + m1]);
+            }
+        }
+    }
+}
+
+fn add_round_join(out: &mut Vec<Vec2>, p: Vec2, m0: Vec2, m1: Vec2, radius: f32, tolerance: f32) {
+    let sweep = m0.perp_dot(m1).atan2(m0.dot(m1));
+    let max_step = if tolerance < radius {
+        2.0 * (1.0 - tolerance / radius).acos()
+    } else {
+        std::f32::consts::FRAC_PI_2
+    };
+    #[allow(clippy::cast_sign_loss)] // There is at least one step.
+    let steps = (sweep.abs() / max_step).ceil().max(1.0) as usize;
+    for i in 0..=steps {
+        let angle = sweep * i as f32 / steps as f32;
+        out.push(p + Vec2::from_angle(angle).rotate(m0));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::{PI, TAU};
+
+    use bevy::math::Vec2;
+    use lyon_tessellation::{FillRule, LineJoin, StrokeOptions};
+
+    use super::offset_polylines;
+    use crate::{geometry::GeometryBuilder, ops::flatten, shapes};
+
+    fn square(half_size: f32) -> Vec<Vec2> {
+        [(-1.0, -1.0), (1.0, -1.0), (1.0, 1.0), (-1.0, 1.0)]
+            .map(|(x, y)| Vec2::new(x, y) * half_size)
+            .to_vec()
+    }
+
+    #[test]
+    fn hole_inset_to_nothing() {
+        let frame = GeometryBuilder::build_as(&shapes::PolygonWithHoles {
+            outer: square(10.0),
+            holes: vec![square(2.0)],
+        });
+
+        let thicker = frame.offset(1.0, LineJoin::Miter);
+        assert!((thicker.area(FillRule::NonZero) - (22.0 * 22.0 - 2.0 * 2.0)).abs() < 1e-3);
+
+        // The hole closes once the distance exceeds half its size.
+        let filled = frame.offset(3.0, LineJoin::Miter);
+        assert!((filled.area(FillRule::NonZero) - 26.0 * 26.0).abs() < 1e-3);
+        assert!(filled.contains_point(Vec2::ZERO, FillRule::NonZero, 0.1));
+        assert_eq!(flatten(&filled.0, 0.1).len(), 1);
+    }
+
+    #[test]
+    fn convex_outset() {
+        let circle = GeometryBuilder::build_as(&shapes::Circle {
+            radius: 10.0,
+            ..shapes::Circle::default()
+        });
+        let grown = circle.offset(5.0, LineJoin::Round);
+        let expected = PI * 15.0 * 15.0;
+        assert!((grown.area(FillRule::NonZero) - expected).abs() < 0.01 * expected);
+        assert_eq!(flatten(&grown.0, 0.1).len(), 1);
+
+        // Going around twice is not convex.
+        let twice = GeometryBuilder::build_as(&shapes::Polygon {
+            points: (0..10)
+                .map(|i| Vec2::from_angle(i as f32 * 0.8 * PI) * 10.0)
+                .collect(),
+            closed: true,
+        });
+        let grown = twice.offset(1.0, LineJoin::Miter);
+        assert!(grown.area(FillRule::NonZero) > twice.area(FillRule::NonZero));
+    }
+
+    #[test]
+    fn jagged_outline() {
+        // Edges much shorter than the distance fold over each other many
+        // times.
+        let points = (0..1000)
+            .map(|i| {
+                let radius = 100.0 + 10.0 * (i as f32 * 0.37).sin();
+                Vec2::from_angle(i as f32 / 1000.0 * TAU) * radius
+            })
+            .collect();
+        let outline = GeometryBuilder::build_as(&shapes::Polygon {
+            points,
+            closed: true,
+        });
+        for (distance, min_radius, max_radius) in [(-4.0, 86.0, 106.0), (4.0, 94.0, 114.0)] {
+            let offset = offset_polylines(
+                &flatten(&outline.0, 0.1),
+                distance,
+                LineJoin::Miter,
+                StrokeOptions::DEFAULT_MITER_LIMIT,
+                0.1,
+            );
+            assert_eq!(offset.len(), 1);
+            let area = offset[0].signed_area();
+            assert!(area > PI * min_radius * min_radius && area < PI * max_radius * max_radius);
+        }
+    }
+
+    #[test]
+    fn region_inset_to_nothing() {
+        let frame = GeometryBuilder::build_as(&shapes::PolygonWithHoles {
+            outer: square(10.0),
+            holes: vec![square(8.0)],
+        });
+        // The region is a band 2 wide, which disappears when both of its
+        // sides move by more than 1.
+        let gone = frame.offset(-1.5, LineJoin::Miter);
+        assert!(flatten(&gone.0, 0.1).is_empty());
+    }
+}
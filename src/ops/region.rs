@@ -0,0 +1,222 @@
+//! Bounds and properties of the region filled by a path.
+
+use bevy::math::{Rect, Vec2};
+use lyon_algorithms::aabb::bounding_box;
+use lyon_tessellation::{FillOptions, FillRule};
+
+use super::{
+    boolean::{is_inside, resolve_rings},
+    flatten, Polyline,
+};
+use crate::{entity::Path, utils::ToVec2};
+
+/// Tolerance used to flatten paths to measure their region, relative to their
+/// size.
+const RELATIVE_TOLERANCE: f32 = 1e-4;
+
+impl Path {
+    /// Returns the smallest axis-aligned rectangle containing this path, or
+    /// an empty rectangle at the origin if the path is empty.
+    ///
+    /// The rectangle contains the curves themselves, not their control
+    /// points.
+    #[must_use]
+    pub fn aabb(&self) -> Rect {
+        let bounds = bounding_box(self.0.iter());
+        Rect::from_corners(bounds.min.to_vec2(), bounds.max.to_vec2())
+    }
+
+    /// Returns the area of the region filled with the given fill rule.
+    ///
+    /// Overlapping parts of the path are only counted once. Curves are
+    /// flattened with a tolerance relative to the size of the path, and never
+    /// coarser than the default tolerance of [`FillOptions`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_prototype_lyon::prelude::*;
+    /// #
+    /// let squares = GeometryBuilder::new()
+    ///     .add(&shapes::Rectangle::new(Rect::new(0.0, 0.0, 10.0, 10.0)))
+    ///     .add(&shapes::Rectangle::new(Rect::new(5.0, 0.0, 15.0, 10.0)))
+    ///     .build();
+    ///
+    /// assert_eq!(squares.area(FillRule::NonZero), 150.0);
+    /// assert_eq!(squares.area(FillRule::EvenOdd), 100.0);
+    /// ```
+    #[must_use]
+    pub fn area(&self, fill_rule: FillRule) -> f32 {
+        self.filled_region(fill_rule)
+            .iter()
+            .map(Polyline::signed_area)
+            .sum()
+    }
+
+    /// Returns the center of mass of the region filled with the default fill
+    /// rule of [`FillOptions`].
+    ///
+    /// Paths enclosing no area, like single lines, return the center of their
+    /// [`aabb`](Self::aabb) instead.
+    #[must_use]
+    pub fn centroid(&self) -> Vec2 {
+        let mut area = 0.0;
+        let mut moment = Vec2::ZERO;
+        for ring in self.filled_region(FillOptions::DEFAULT_FILL_RULE) {
+            let n = ring.points.len();
+            for i in 0..n {
+                let (a, b) = (ring.points[i], ring.points[(i + 1) % n]);
+                let cross = a.perp_dot(b);
+                area += cross;
+                moment += (a + b) * cross;
+            }
+        }
+        if area.abs() <= f32::EPSILON {
+            return self.aabb().center();
+        }
+        // Both sums are twice the area and six times the moment of the region.
+        moment / (3.0 * area)
+    }
+
+    /// Returns the length of the outline of the region filled with the default
+    /// fill rule of [`FillOptions`], holes included.
+    ///
+    /// Unlike [`length`](Self::length), open sub-paths are considered closed
+    /// and overlapping parts are ignored.
+    #[must_use]
+    pub fn perimeter(&self) -> f32 {
+        self.filled_region(FillOptions::DEFAULT_FILL_RULE)
+            .iter()
+            .map(|ring| {
+                let n = ring.points.len();
+                (0..n)
+                    .map(|i| ring.points[i].distance(ring.points[(i + 1) % n]))
+                    .sum::<f32>()
+            })
+            .sum()
+    }
+
+    /// Returns the outline of the filled region, as rings with the region on
+    /// their left.
+    fn filled_region(&self, fill_rule: FillRule) -> Vec<Polyline> {
+        let size = self.aabb().size().max_element();
+        let tolerance = (size * RELATIVE_TOLERANCE).clamp(1e-6, FillOptions::DEFAULT_TOLERANCE);
+        let rings = flatten(&self.0, tolerance);
+        resolve_rings(rings, |winding| is_inside(winding, fill_rule))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::{PI, TAU};
+
+    use bevy::math::{Rect, Vec2};
+    use lyon_tessellation::FillRule;
+
+    use crate::{entity::Path, geometry::GeometryBuilder, ops::TestRng, shapes};
+
+    fn polygon(points: Vec<Vec2>) -> Path {
+        GeometryBuilder::build_as(&shapes::Polygon {
+            points,
+            closed: true,
+        })
+    }
+
+    fn corners(rect: Rect) -> Vec<Vec2> {
+        vec![
+            rect.min,
+            Vec2::new(rect.max.x, rect.min.y),
+            rect.max,
+            Vec2::new(rect.min.x, rect.max.y),
+        ]
+    }
+
+    fn assert_close(actual: f32, expected: f32, relative: f32) {
+        assert!(
+            (actual - expected).abs() <= relative * expected.abs(),
+            "{actual} is not close to {expected}"
+        );
+    }
+
+    #[test]
+    fn scaled_polygons() {
+        let mut rng = TestRng::new(0x9e37_79b9);
+        for scale in [0.01, 1.0, 100.0] {
+            for _ in 0..25 {
+                // Star-shaped polygons around the origin are simple.
+                #[allow(clippy::cast_sign_loss)] // The range is positive.
+                let sides = 3 + (rng.range(0.0..10.0) as usize);
+                let mut points = (0..sides)
+                    .map(|i| {
+                        let angle = (i as f32 + rng.range(0.0..0.9)) * TAU / sides as f32;
+                        Vec2::from_angle(angle) * rng.range(1.0..10.0) * scale
+                    })
+                    .collect::<Vec<_>>();
+                if rng.range(0.0..1.0) < 0.5 {
+                    points.reverse();
+                }
+                let expected = (0..sides)
+                    .map(|i| points[i].perp_dot(points[(i + 1) % sides]))
+                    .sum::<f32>()
+                    .abs()
+                    * 0.5;
+                let path = polygon(points);
+                assert_close(path.area(FillRule::NonZero), expected, 1e-4);
+                assert_close(path.area(FillRule::EvenOdd), expected, 1e-4);
+            }
+        }
+    }
+
+    #[test]
+    fn circles() {
+        for radius in [0.01, 1.0, 100.0] {
+            let center = Vec2::new(3.0, -2.0) * radius;
+            let circle = GeometryBuilder::build_as(&shapes::Circle {
+                radius,
+                center,
+                ..shapes::Circle::default()
+            });
+            assert_close(circle.area(FillRule::NonZero), PI * radius * radius, 1e-3);
+            assert_close(circle.perimeter(), TAU * radius, 1e-3);
+            assert!(circle.centroid().distance(center) < 1e-4 * radius);
+        }
+    }
+
+    #[test]
+    fn superellipse() {
+        // The area of |x / a|^n + |y / b|^n = 1 is 4ab Γ(1 + 1/n)² / Γ(1 + 2/n),
+        // which is 2ab / 3 for n = 0.5.
+        let path = GeometryBuilder::build_as(&shapes::Superellipse {
+            radii: Vec2::new(100.0, 50.0),
+            exponent: 0.5,
+            ..shapes::Superellipse::default()
+        });
+        assert_close(path.area(FillRule::NonZero), 2.0 * 100.0 * 50.0 / 3.0, 0.01);
+        assert!(path.centroid().length() < 0.01);
+    }
+
+    #[test]
+    fn nested_rings() {
+        // Both squares go counter-clockwise.
+        let path = GeometryBuilder::new()
+            .add(&shapes::Rectangle::new(Rect::new(0.0, 0.0, 10.0, 10.0)))
+            .add(&shapes::Rectangle::new(Rect::new(2.0, 2.0, 4.0, 4.0)))
+            .build();
+        assert_close(path.area(FillRule::NonZero), 100.0, 1e-6);
+        assert_close(path.area(FillRule::EvenOdd), 96.0, 1e-6);
+        // The default fill rule is even-odd.
+        assert_close(path.perimeter(), 48.0, 1e-6);
+    }
+
+    #[test]
+    fn centroid_with_hole() {
+        let path = GeometryBuilder::build_as(&shapes::PolygonWithHoles {
+            outer: corners(Rect::new(0.0, 0.0, 10.0, 10.0)),
+            holes: vec![corners(Rect::new(6.0, 4.0, 8.0, 6.0))],
+        });
+        let expected = (100.0 * Vec2::new(5.0, 5.0) - 4.0 * Vec2::new(7.0, 5.0)) / 96.0;
+        assert!(path.centroid().distance(expected) < 1e-4);
+        assert_close(path.area(FillRule::NonZero), 96.0, 1e-6);
+    }
+}
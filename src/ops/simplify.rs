@@ -0,0 +1,182 @@
+//! Simplification and smoothing of paths.
+
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+use bevy::math::Vec2;
+use lyon_tessellation::{path::path::Builder, FillOptions};
+
+use super::{build_path, flatten, segment_distance, Polyline};
+use crate::{entity::Path, geometry::Geometry, shapes::Spline};
+
+/// The algorithm used by [`Path::simplified`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SimplifyMethod {
+    /// Ramer–Douglas–Peucker: keeps the points farther than the tolerance
+    /// from the simplified outline. Preserves sharp features.
+    #[default]
+    RamerDouglasPeucker,
+    /// Visvalingam–Whyatt: repeatedly removes the point forming the smallest
+    /// triangle with its neighbours, while its area is under the square of
+    /// the tolerance. Removes small details more evenly.
+    VisvalingamWhyatt,
+}
+
+impl Path {
+    /// Returns a copy of this path made of fewer line segments, deviating
+    /// from it by about `tolerance`.
+    ///
+    /// Curves are flattened first. The first and last points of open
+    /// sub-paths are always kept.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_prototype_lyon::prelude::*;
+    /// # use bevy_prototype_lyon::ops::SimplifyMethod;
+    /// #
+    /// // A wobbly stroke recorded from mouse input.
+    /// let points = (0..1000)
+    ///     .map(|i| Vec2::new(i as f32, (i as f32 * 0.7).sin() * 0.2))
+    ///     .collect();
+    /// let recorded = GeometryBuilder::build_as(&shapes::Polygon {
+    ///     points,
+    ///     closed: false,
+    /// });
+    ///
+    /// let simplified = recorded.simplified(1.0, SimplifyMethod::RamerDouglasPeucker);
+    /// # assert_eq!(simplified.0.iter().count(), 3);
+    /// ```
+    #[must_use]
+    pub fn simplified(&self, tolerance: f32, method: SimplifyMethod) -> Self {
+        let polylines = flatten(&self.0, FillOptions::DEFAULT_TOLERANCE)
+            .into_iter()
+            .map(|polyline| Polyline {
+                points: match method {
+                    SimplifyMethod::RamerDouglasPeucker => {
+                        ramer_douglas_peucker(&polyline.points, polyline.closed, tolerance)
+                    }
+                    SimplifyMethod::VisvalingamWhyatt => visvalingam_whyatt(
+                        &polyline.points,
+                        polyline.closed,
+                        tolerance * tolerance,
+                    ),
+                },
+                closed: polyline.closed,
+            })
+            .collect::<Vec<_>>();
+        Self(build_path(&polylines))
+    }
+
+    /// Returns a smooth curve passing through every point of this path.
+    ///
+    /// Curves are flattened first, then every sub-path is replaced with a
+    /// centripetal [`Spline`] through its points. This is usually applied
+    /// after [`simplified`](Self::simplified).
+    #[must_use]
+    pub fn smoothed(&self) -> Self {
+        let mut b = Builder::new();
+        for polyline in flatten(&self.0, FillOptions::DEFAULT_TOLERANCE) {
+            Spline {
+                points: polyline.points,
+                closed: polyline.closed,
+                ..Spline::default()
+            }
+            .add_geometry(&mut b);
+        }
+        Self(b.build())
+    }
+}
+
+fn ramer_douglas_peucker(points: &[Vec2], closed: bool, tolerance: f32) -> Vec<Vec2> {
+    let n = points.len();
+    if n < 3 {
+        return points.to_vec();
+    }
+
+    let mut keep = vec![false; n];
+    keep[0] = true;
+    // Ranges of indices, where `n` stands for the first point of closed rings.
+    let mut ranges = if closed {
+        // Rings are split at the point farthest from the first one.
+        let far = (1..n)
+            .max_by(|&a, &b| {
+                let distance = |i: usize| points[0].distance_squared(points[i]);
+                distance(a).total_cmp(&distance(b))
+            })
+            .unwrap_or(1);
+        keep[far] = true;
+        vec![(0, far), (far, n)]
+    } else {
+        keep[n - 1] = true;
+        vec![(0, n - 1)]
+    };
+
+    while let Some((start, end)) = ranges.pop() {
+        let (a, b) = (points[start], points[end % n]);
+        let farthest = (start + 1..end)
+            .map(|i| (i, segment_distance(points[i], a, b)))
+            .max_by(|x, y| x.1.total_cmp(&y.1));
+        if let Some((i, distance)) = farthest {
+            if distance > tolerance {
+                keep[i] = true;
+                ranges.extend([(start, i), (i, end)]);
+            }
+        }
+    }
+
+    points
+        .iter()
+        .zip(keep)
+        .filter_map(|(point, keep)| keep.then_some(*point))
+        .collect()
+}
+
+fn visvalingam_whyatt(points: &[Vec2], closed: bool, min_area: f32) -> Vec<Vec2> {
+    let n = points.len();
+    let min_points = if closed { 3 } else { 2 };
+    if n <= min_points {
+        return points.to_vec();
+    }
+
+    // The remaining points form a linked list.
+    let mut prev = (0..n).map(|i| (i + n - 1) % n).collect::<Vec<_>>();
+    let mut next = (0..n).map(|i| (i + 1) % n).collect::<Vec<_>>();
+    let mut removed = vec![false; n];
+    let removable = |i: usize| closed || (i != 0 && i != n - 1);
+    // The bits of non-negative floats are ordered like the floats themselves.
+    let area = |prev: usize, i: usize, next: usize| {
+        ((points[prev] - points[i]).perp_dot(points[next] - points[i]).abs() * 0.5).to_bits()
+    };
+
+    let mut heap = (0..n)
+        .filter(|&i| removable(i))
+        .map(|i| Reverse((area(prev[i], i, next[i]), i)))
+        .collect::<BinaryHeap<_>>();
+    let mut remaining = n;
+    while let Some(Reverse((bits, i))) = heap.pop() {
+        if removed[i] || bits != area(prev[i], i, next[i]) {
+            // The entry is outdated.
+            continue;
+        }
+        if f32::from_bits(bits) >= min_area || remaining == min_points {
+            break;
+        }
+        removed[i] = true;
+        remaining -= 1;
+        let (p, q) = (prev[i], next[i]);
+        next[p] = q;
+        prev[q] = p;
+        for j in [p, q] {
+            if removable(j) {
+                heap.push(Reverse((area(prev[j], j, next[j]), j)));
+            }
+        }
+    }
+
+    points
+        .iter()
+        .zip(removed)
+        .filter_map(|(point, removed)| (!removed).then_some(*point))
+        .collect()
+}
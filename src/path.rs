@@ -39,9 +39,8 @@ impl ShapePath {
 /// #
 /// fn my_system(mut query: Query<&mut Path, With<Player>>) {
 ///     if let Some(mut path) = query.iter_mut().next() {
-///         let square = shapes::Rectangle {
-///             rect: Rect::from_center_size(Vec2::ZERO, Vec2::splat(50.0)),
-///         };
+///         let square =
+///             shapes::Rectangle::new(Rect::from_center_size(Vec2::ZERO, Vec2::splat(50.0)));
 ///         let triangle = shapes::RegularPolygon {
 ///             sides: 3,
 ///             center: Vec2::new(100.0, 0.0),
@@ -0,0 +1,213 @@
+//! A [`bevy::picking`] backend for shapes.
+//!
+//! Hits are tested against the exact geometry of the shapes, as drawn, with
+//! [`ShapeHitTest`]: holes and thin strokes are handled like the rendered
+//! shape, instead of using the triangles of the mesh. Fills and strokes whose
+//! brush is fully transparent are not hit, unless the stroke is textured.
+//!
+//! Picking can be disabled for individual shapes by adding
+//! [`Pickable::IGNORE`]. To make shape picking opt-in, set
+//! [`ShapePickingSettings::require_markers`] to `true` and add
+//! [`ShapePickingCamera`] and [`Pickable`] components to the desired cameras
+//! and shapes.
+//!
+//! ## Implementation Notes
+//!
+//! - The `position` reported in `HitData` is in world space, and the `normal`
+//!   is the back direction of the `GlobalTransform` of the shape.
+//! - Do not add `MeshPickingPlugin` for the same cameras, or shapes would be
+//!   hit twice.
+//! - The outline tested for each shape is derived from its [`Path`],
+//!   [`Stroke`], [`PathTrim`] and [`ShapeAnchor`], and kept until one of them
+//!   changes.
+
+use bevy::{
+    app::{App, Plugin, PreUpdate},
+    camera::{
+        visibility::{RenderLayers, ViewVisibility},
+        Camera,
+    },
+    ecs::{
+        component::Component,
+        entity::Entity,
+        lifecycle::RemovedComponents,
+        message::MessageWriter,
+        query::{Changed, Has, Or, Without},
+        reflect::{ReflectComponent, ReflectResource},
+        resource::Resource,
+        schedule::IntoScheduleConfigs as _,
+        system::{Commands, Query, Res},
+    },
+    math::{Ray3d, Vec3},
+    picking::backend::prelude::*,
+    reflect::{std_traits::ReflectDefault, Reflect},
+};
+
+use crate::{
+    draw::{PathTrim, ShapeAnchor, Stroke},
+    entity::Path,
+    ops::{ShapeGeometry, ShapeHitTest, ShapeHitTestItem},
+};
+
+/// An optional component that marks cameras that should be used in the
+/// [`ShapePickingPlugin`].
+///
+/// Only needed if [`ShapePickingSettings::require_markers`] is set to `true`,
+/// and ignored otherwise.
+#[derive(Debug, Clone, Default, Component, Reflect)]
+#[reflect(Debug, Default, Component)]
+pub struct ShapePickingCamera;
+
+/// Runtime settings for the [`ShapePickingPlugin`].
+#[derive(Debug, Clone, Default, Resource, Reflect)]
+#[reflect(Resource, Default)]
+pub struct ShapePickingSettings {
+    /// When set to `true`, only cameras marked with [`ShapePickingCamera`] and
+    /// shapes marked with [`Pickable`] are considered. `false` by default.
+    pub require_markers: bool,
+}
+
+/// Adds the shape picking backend to the app.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ShapePickingPlugin;
+
+impl Plugin for ShapePickingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ShapePickingSettings>()
+            .register_type::<ShapePickingCamera>()
+            .register_type::<ShapePickingSettings>()
+            .add_systems(
+                PreUpdate,
+                (update_shape_geometry, shape_picking)
+                    .chain()
+                    .in_set(PickingSystems::Backend),
+            );
+    }
+}
+
+/// The geometry a shape is picked with.
+#[derive(Component)]
+struct PickedGeometry(ShapeGeometry);
+
+/// Derives the geometry of the shapes that have none yet, or whose outline
+/// changed.
+#[allow(clippy::type_complexity)]
+fn update_shape_geometry(
+    mut commands: Commands,
+    changed: Query<
+        (Entity, ShapeHitTest),
+        Or<(
+            Without<PickedGeometry>,
+            Changed<Path>,
+            Changed<Stroke>,
+            Changed<PathTrim>,
+            Changed<ShapeAnchor>,
+        )>,
+    >,
+    shapes: Query<ShapeHitTest>,
+    mut removed_strokes: RemovedComponents<Stroke>,
+    mut removed_trims: RemovedComponents<PathTrim>,
+    mut removed_anchors: RemovedComponents<ShapeAnchor>,
+) {
+    for (entity, shape) in &changed {
+        commands
+            .entity(entity)
+            .insert(PickedGeometry(ShapeGeometry::new(&shape)));
+    }
+    let removed = removed_strokes
+        .read()
+        .chain(removed_trims.read())
+        .chain(removed_anchors.read());
+    for entity in removed {
+        if let Ok(shape) = shapes.get(entity) {
+            commands
+                .entity(entity)
+                .insert(PickedGeometry(ShapeGeometry::new(&shape)));
+        }
+    }
+}
+
+/// Tests the pointer rays against the shapes and sends [`PointerHits`].
+#[allow(clippy::type_complexity)]
+fn shape_picking(
+    settings: Res<ShapePickingSettings>,
+    ray_map: Res<RayMap>,
+    cameras: Query<(&Camera, Has<ShapePickingCamera>, Option<&RenderLayers>)>,
+    shapes: Query<(
+        Entity,
+        ShapeHitTest,
+        &PickedGeometry,
+        &ViewVisibility,
+        Option<&Pickable>,
+        Option<&RenderLayers>,
+    )>,
+    mut pointer_hits_writer: MessageWriter<PointerHits>,
+) {
+    for (&ray_id, &ray) in ray_map.iter() {
+        let Ok((camera, camera_can_pick, camera_layers)) = cameras.get(ray_id.camera) else {
+            continue;
+        };
+        if settings.require_markers && !camera_can_pick {
+            continue;
+        }
+        let camera_layers = camera_layers.cloned().unwrap_or_default();
+
+        let mut hits = Vec::new();
+        for (entity, shape, geometry, visibility, pickable, layers) in &shapes {
+            let pickable_shape = visibility.get()
+                && (!settings.require_markers || pickable.is_some())
+                && pickable.is_none_or(|pickable| pickable.is_hoverable)
+                && camera_layers.intersects(&layers.cloned().unwrap_or_default());
+            let Some(position) = pickable_shape
+                .then(|| ray_hit(&shape, &geometry.0, ray))
+                .flatten()
+            else {
+                continue;
+            };
+            let depth = ray.origin.distance(position);
+            let normal = *shape.transform.back();
+            let blocks = pickable.is_none_or(|pickable| pickable.should_block_lower);
+            let hit = HitData::new(ray_id.camera, depth, Some(position), Some(normal));
+            hits.push((entity, hit, blocks));
+        }
+        hits.sort_by(|a, b| a.1.depth.total_cmp(&b.1.depth));
+
+        // Shapes behind the first blocking one are not hit.
+        if let Some(first_blocking) = hits.iter().position(|&(_, _, blocks)| blocks) {
+            hits.truncate(first_blocking + 1);
+        }
+        let picks = hits
+            .into_iter()
+            .map(|(entity, hit, _)| (entity, hit))
+            .collect::<Vec<_>>();
+        if !picks.is_empty() {
+            pointer_hits_writer.write(PointerHits::new(ray_id.pointer, picks, camera.order as f32));
+        }
+    }
+}
+
+/// Returns where `ray` hits the shape, in world space.
+fn ray_hit(shape: &ShapeHitTestItem, geometry: &ShapeGeometry, ray: Ray3d) -> Option<Vec3> {
+    if shape.transform.affine().is_nan() {
+        return None;
+    }
+    // Intersect the ray with the plane of the shape, in its local space.
+    let to_local = shape.transform.affine().inverse();
+    let origin = to_local.transform_point3(ray.origin);
+    let direction = to_local.transform_vector3(*ray.direction);
+    let distance = -origin.z / direction.z;
+    if !distance.is_finite() || distance < 0.0 {
+        return None;
+    }
+    let local = (origin + direction * distance).truncate();
+
+    let fill = shape
+        .fill_options()
+        .filter(|_| shape.fill.is_none_or(|fill| !fill.brush.is_transparent()));
+    let stroke = shape
+        .stroke
+        .filter(|stroke| stroke.texture.is_some() || !stroke.brush.is_transparent());
+    geometry
+        .hit(local, fill, stroke)
+        .then(|| shape.transform.transform_point(local.extend(0.0)))
+}
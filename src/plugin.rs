@@ -13,16 +13,17 @@
 
 use bevy::{
     app::{App, Plugin}, asset::Assets, color::palettes::css::FUCHSIA, ecs::{
-        query::{Changed, Or}, schedule::IntoScheduleConfigs as _, system::{Query, ResMut}
-    }, log, mesh::Indices, prelude::{
+        entity::{Entity, EntityHashSet}, lifecycle::RemovedComponents, query::{Changed, Or}, schedule::IntoScheduleConfigs as _, system::{Query, ResMut}
+    }, log, math::Vec2, mesh::Indices, prelude::{
         Color, Deref, DerefMut, Mesh, Mesh2d, MeshMaterial2d, PostUpdate, Resource, SystemSet
     }, render::render_resource::PrimitiveTopology
 };
 use bevy::asset::RenderAssetUsages;
-use lyon_tessellation::{self as tess, BuffersBuilder};
+use lyon_algorithms::length::approximate_length;
+use lyon_tessellation::{self as tess, BuffersBuilder, FillOptions};
 
 use crate::{
-    brush::{Brush, Gradient, GradientStop, LinearGradient}, draw::{Fill, Stroke}, entity::Path, render::{GradientMaterial, GradientMaterialPlugin}, vertex::{VertexBuffers, VertexConstructor}
+    brush::{Brush, Gradient, GradientStop, LinearGradient}, draw::{Fill, PathTrim, ShapeAnchor, Stroke, StrokeAlignment, StrokeTexture, StrokeTextureMode}, entity::Path, ops::{markers::marker_path, offset::aligned_stroke_path, split_sub_paths}, render::{GradientMaterial, GradientMaterialPlugin}, vertex::{VertexBuffers, VertexConstructor}
 };
 
 /// A plugin that provides resources and a system to draw shapes in Bevy with
@@ -43,6 +44,11 @@ impl Plugin for ShapePlugin {
             .add_plugins(GradientMaterialPlugin)
             .register_type::<Fill>()
             .register_type::<Stroke>()
+            .register_type::<PathTrim>()
+            .register_type::<ShapeAnchor>()
+            .register_type::<StrokeAlignment>()
+            .register_type::<StrokeTexture>()
+            .register_type::<StrokeTextureMode>()
             .register_type::<Gradient>()
             .register_type::<GradientStop>()
             .register_type::<Brush>()
@@ -57,58 +63,117 @@ pub struct BuildShapes;
 
 /// Queries all the [`ShapeBundle`]s to mesh them when they are added
 /// or re-mesh them when they are changed.
-#[allow(clippy::type_complexity)]
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
 fn mesh_shapes_system(
     mut meshes: ResMut<Assets<Mesh>>,
     mut gradients: ResMut<Assets<GradientMaterial>>,
     mut fill_tess: ResMut<FillTessellator>,
     mut stroke_tess: ResMut<StrokeTessellator>,
-    mut query: Query<
-        (
-            Option<&Fill>,
-            Option<&Stroke>,
-            &Path,
-            &mut Mesh2d,
-            &mut MeshMaterial2d<GradientMaterial>,
-        ),
-        Or<(Changed<Path>, Changed<Fill>, Changed<Stroke>)>,
+    changed: Query<
+        Entity,
+        Or<(
+            Changed<Path>,
+            Changed<Fill>,
+            Changed<Stroke>,
+            Changed<PathTrim>,
+            Changed<ShapeAnchor>,
+        )>,
     >,
+    mut removed_trims: RemovedComponents<PathTrim>,
+    mut removed_anchors: RemovedComponents<ShapeAnchor>,
+    mut query: Query<(
+        Option<&Fill>,
+        Option<&Stroke>,
+        &Path,
+        Option<&PathTrim>,
+        Option<&ShapeAnchor>,
+        &mut Mesh2d,
+        &mut MeshMaterial2d<GradientMaterial>,
+    )>,
 ) {
-    for (maybe_fill_mode, maybe_stroke_mode, path, mut mesh, mut material) in &mut query {
-        let mut buffers = VertexBuffers::new();
+    // Removing a component is not a change, so the shapes that lost one are
+    // re-meshed as well.
+    let entities = changed
+        .iter()
+        .chain(removed_trims.read())
+        .chain(removed_anchors.read())
+        .collect::<EntityHashSet>();
+    for entity in entities {
+        let Ok((
+            maybe_fill_mode,
+            maybe_stroke_mode,
+            path,
+            maybe_trim,
+            maybe_anchor,
+            mut mesh,
+            mut material,
+        )) = query.get_mut(entity)
+        else {
+            continue;
+        };
+        let offset = maybe_anchor.map_or(Vec2::ZERO, |anchor| anchor.offset(path));
 
-        if let Some(fill_mode) = maybe_fill_mode {
-            fill(&mut fill_tess, &path.0, fill_mode, &mut buffers);
-        }
-
-        if let Some(stroke_mode) = maybe_stroke_mode {
-            stroke(&mut stroke_tess, &path.0, stroke_mode, &mut buffers);
-        }
+        let trimmed;
+        let path = match maybe_trim {
+            Some(trim) => {
+                trimmed = trim.apply(path);
+                &trimmed
+            }
+            None => path,
+        };
 
-        if (maybe_fill_mode, maybe_stroke_mode) == (None, None) {
-            fill(
-                &mut fill_tess,
-                &path.0,
-                &Fill::brush(Color::from(FUCHSIA)),
-                &mut buffers,
-            );
-        }
+        let buffers = tessellate(
+            &mut fill_tess,
+            &mut stroke_tess,
+            &path.0,
+            maybe_fill_mode,
+            maybe_stroke_mode,
+        );
 
-        mesh.0 = meshes.add(build_mesh(&buffers));
+        let stroke_texture = maybe_stroke_mode.and_then(|mode| mode.texture.as_ref());
+        mesh.0 = meshes.add(build_mesh(&buffers, stroke_texture.is_some(), offset));
         // fill 与 stroke 可以兼得，但我懒了() 
-        if let Some(mode) = maybe_fill_mode {
-            *material = MeshMaterial2d(gradients.add(GradientMaterial {
-                uniform: mode.brush.clone_as_uniform(),
-            }));
-        }
-        else if let Some(mode) = maybe_stroke_mode {
-            *material = MeshMaterial2d(gradients.add(GradientMaterial {
-                uniform: mode.brush.clone_as_uniform(),
-            }));
+        let brush = maybe_fill_mode
+            .map(|mode| &mode.brush)
+            .or_else(|| maybe_stroke_mode.map(|mode| &mode.brush));
+        if let Some(brush) = brush {
+            *material =
+                MeshMaterial2d(gradients.add(GradientMaterial::new(brush, stroke_texture, offset)));
         }
     }
 }
 
+/// Tessellates the fill and the stroke of a shape, or a placeholder fill if
+/// it has neither.
+fn tessellate(
+    fill_tess: &mut ResMut<FillTessellator>,
+    stroke_tess: &mut ResMut<StrokeTessellator>,
+    path: &tess::path::Path,
+    maybe_fill_mode: Option<&Fill>,
+    maybe_stroke_mode: Option<&Stroke>,
+) -> VertexBuffers {
+    let mut buffers = VertexBuffers::new();
+
+    if let Some(fill_mode) = maybe_fill_mode {
+        fill(fill_tess, path, fill_mode, &mut buffers);
+    }
+
+    if let Some(stroke_mode) = maybe_stroke_mode {
+        stroke(stroke_tess, fill_tess, path, stroke_mode, &mut buffers);
+    }
+
+    if (maybe_fill_mode, maybe_stroke_mode) == (None, None) {
+        fill(
+            fill_tess,
+            path,
+            &Fill::brush(Color::from(FUCHSIA)),
+            &mut buffers,
+        );
+    }
+
+    buffers
+}
+
 #[allow(clippy::trivially_copy_pass_by_ref)] // lyon takes &FillOptions
 fn fill(
     tess: &mut ResMut<FillTessellator>,
@@ -119,7 +184,7 @@ fn fill(
     if let Err(e) = tess.tessellate_path(
         path,
         &mode.options,
-        &mut BuffersBuilder::new(buffers, VertexConstructor { brush: &mode.brush }),
+        &mut BuffersBuilder::new(buffers, VertexConstructor::default()),
     ) {
         log::error!("FillTessellator error: {:?}", e);
     }
@@ -128,20 +193,73 @@ fn fill(
 #[allow(clippy::trivially_copy_pass_by_ref)] // lyon takes &StrokeOptions
 fn stroke(
     tess: &mut ResMut<StrokeTessellator>,
+    fill_tess: &mut ResMut<FillTessellator>,
     path: &tess::path::Path,
     mode: &Stroke,
     buffers: &mut VertexBuffers,
+) {
+    if let Some(markers) = marker_path(path, mode) {
+        let options = FillOptions::tolerance(mode.options.tolerance);
+        if let Err(e) = fill_tess.tessellate_path(
+            &markers,
+            &options,
+            &mut BuffersBuilder::new(buffers, VertexConstructor::default()),
+        ) {
+            log::error!("FillTessellator error: {:?}", e);
+        }
+    }
+
+    let aligned;
+    let path = if mode.alignment == StrokeAlignment::Center {
+        path
+    } else {
+        aligned = aligned_stroke_path(path, &mode.options, mode.alignment);
+        &aligned
+    };
+
+    match mode.texture.as_ref().map(|texture| texture.mode) {
+        None => tessellate_stroke(tess, path, mode, None, buffers),
+        Some(StrokeTextureMode::Tile(length)) => {
+            tessellate_stroke(tess, path, mode, Some(u_scale(length)), buffers);
+        }
+        Some(StrokeTextureMode::Stretch) => {
+            // Each sub-path is tessellated on its own to know its length.
+            for sub_path in split_sub_paths(path) {
+                let length = approximate_length(&sub_path, mode.options.tolerance);
+                tessellate_stroke(tess, &sub_path, mode, Some(u_scale(length)), buffers);
+            }
+        }
+    }
+}
+
+/// Returns the U coordinate per unit of length, or zero for lengths that
+/// cannot be divided by, so that the texture coordinates stay finite. This is
+/// the only place where tile lengths are validated.
+fn u_scale(length: f32) -> f32 {
+    if length > 0.0 {
+        length.recip()
+    } else {
+        0.0
+    }
+}
+
+fn tessellate_stroke(
+    tess: &mut ResMut<StrokeTessellator>,
+    path: &tess::path::Path,
+    mode: &Stroke,
+    u_scale: Option<f32>,
+    buffers: &mut VertexBuffers,
 ) {
     if let Err(e) = tess.tessellate_path(
         path,
         &mode.options,
-        &mut BuffersBuilder::new(buffers, VertexConstructor { brush: &mode.brush }),
+        &mut BuffersBuilder::new(buffers, VertexConstructor { u_scale }),
     ) {
         log::error!("StrokeTessellator error: {:?}", e);
     }
 }
 
-fn build_mesh(buffers: &VertexBuffers) -> Mesh {
+fn build_mesh(buffers: &VertexBuffers, textured: bool, offset: Vec2) -> Mesh {
     let mut mesh = Mesh::new(PrimitiveTopology::TriangleList, RenderAssetUsages::default());
     mesh.insert_indices(Indices::U32(buffers.indices.clone()));
     mesh.insert_attribute(
@@ -150,10 +268,16 @@ fn build_mesh(buffers: &VertexBuffers) -> Mesh {
             .vertices
             .iter()
             .map(|v| {
-                [v.position[0], v.position[1], 0.0]
+                [v.position[0] + offset.x, v.position[1] + offset.y, 0.0]
             })
             .collect::<Vec<[f32; 3]>>(),
     );
+    if textured {
+        mesh.insert_attribute(
+            Mesh::ATTRIBUTE_UV_0,
+            buffers.vertices.iter().map(|v| v.uv).collect::<Vec<[f32; 2]>>(),
+        );
+    }
 
     mesh
 }
@@ -163,3 +287,95 @@ struct FillTessellator(lyon_tessellation::FillTessellator);
 
 #[derive(Resource, Deref, DerefMut)]
 struct StrokeTessellator(lyon_tessellation::StrokeTessellator);
+
+#[cfg(test)]
+mod tests {
+    use bevy::{
+        asset::{Assets, Handle},
+        color::Color,
+        ecs::{entity::Entity, system::SystemId, world::World},
+        math::{Rect, Vec2},
+        prelude::{Mesh, Mesh2d, MeshMaterial2d},
+    };
+
+    use super::{mesh_shapes_system, u_scale, FillTessellator, StrokeTessellator};
+    use crate::{
+        draw::{Fill, PathTrim, ShapeAnchor, Stroke},
+        geometry::GeometryBuilder,
+        render::GradientMaterial,
+        shapes,
+    };
+
+    fn setup() -> (World, SystemId) {
+        let mut world = World::new();
+        world.insert_resource(Assets::<Mesh>::default());
+        world.insert_resource(Assets::<GradientMaterial>::default());
+        world.insert_resource(FillTessellator(lyon_tessellation::FillTessellator::new()));
+        world.insert_resource(StrokeTessellator(
+            lyon_tessellation::StrokeTessellator::new(),
+        ));
+        let system = world.register_system(mesh_shapes_system);
+        (world, system)
+    }
+
+    /// Returns the bounds of the mesh of `entity`.
+    fn mesh_bounds(world: &World, entity: Entity) -> Rect {
+        let handle = &world.get::<Mesh2d>(entity).unwrap().0;
+        let mesh = world.resource::<Assets<Mesh>>().get(handle).unwrap();
+        let positions = mesh
+            .attribute(Mesh::ATTRIBUTE_POSITION)
+            .and_then(|positions| positions.as_float3())
+            .unwrap();
+        positions.iter().fold(Rect::EMPTY, |bounds, &[x, y, _]| {
+            bounds.union_point(Vec2::new(x, y))
+        })
+    }
+
+    #[test]
+    fn removed_anchor() {
+        let (mut world, system) = setup();
+        let entity = world
+            .spawn((
+                GeometryBuilder::build_as(&shapes::Rectangle::new(Rect::new(0.0, 0.0, 10.0, 10.0))),
+                Fill::brush(Color::BLACK),
+                ShapeAnchor::CENTER,
+                Mesh2d(Handle::default()),
+                MeshMaterial2d::<GradientMaterial>(Handle::default()),
+            ))
+            .id();
+        world.run_system(system).unwrap();
+        assert_eq!(mesh_bounds(&world, entity), Rect::new(-5.0, -5.0, 5.0, 5.0));
+
+        world.entity_mut(entity).remove::<ShapeAnchor>();
+        world.run_system(system).unwrap();
+        assert_eq!(mesh_bounds(&world, entity), Rect::new(0.0, 0.0, 10.0, 10.0));
+    }
+
+    #[test]
+    fn removed_trim() {
+        let (mut world, system) = setup();
+        let entity = world
+            .spawn((
+                GeometryBuilder::build_as(&shapes::Line(Vec2::ZERO, Vec2::new(10.0, 0.0))),
+                Stroke::new(Color::BLACK, 2.0),
+                PathTrim::new(0.0, 0.5),
+                Mesh2d(Handle::default()),
+                MeshMaterial2d::<GradientMaterial>(Handle::default()),
+            ))
+            .id();
+        world.run_system(system).unwrap();
+        assert_eq!(mesh_bounds(&world, entity), Rect::new(0.0, -1.0, 5.0, 1.0));
+
+        world.entity_mut(entity).remove::<PathTrim>();
+        world.run_system(system).unwrap();
+        assert_eq!(mesh_bounds(&world, entity), Rect::new(0.0, -1.0, 10.0, 1.0));
+    }
+
+    #[test]
+    fn tile_lengths() {
+        assert!((u_scale(4.0) - 0.25).abs() < f32::EPSILON);
+        for length in [0.0, -1.0, f32::NAN] {
+            assert!(u_scale(length).abs() < f32::EPSILON, "{length}");
+        }
+    }
+}
@@ -0,0 +1,227 @@
+//! [`Geometry`] implementations for the 2D primitives of [`bevy::math`].
+//!
+//! Primitives are drawn centered on the origin, like Bevy's own meshes of the
+//! same primitives. Unbounded primitives, like `Line2d` and `Plane2d`, cannot
+//! be drawn.
+
+use std::f32::consts::FRAC_PI_2;
+
+use bevy::math::{
+    primitives::{
+        Annulus, Arc2d, Capsule2d, Circle, CircularSector, CircularSegment, ConvexPolygon, Ellipse,
+        Polygon, Polyline2d, Primitive2d, Rectangle, RegularPolygon, Rhombus, Ring, Segment2d,
+        Triangle2d,
+    },
+    Rect, Vec2,
+};
+use lyon_tessellation::{
+    path::{path::Builder, Path as LyonPath},
+    FillOptions,
+};
+
+use crate::{geometry::Geometry, ops::path_signed_area, shapes};
+
+impl Geometry for Circle {
+    fn add_geometry(&self, b: &mut Builder) {
+        shapes::Circle {
+            radius: self.radius,
+            ..shapes::Circle::default()
+        }
+        .add_geometry(b);
+    }
+}
+
+impl Geometry for Ellipse {
+    fn add_geometry(&self, b: &mut Builder) {
+        shapes::Ellipse {
+            radii: self.half_size,
+            ..shapes::Ellipse::default()
+        }
+        .add_geometry(b);
+    }
+}
+
+impl Geometry for Rectangle {
+    fn add_geometry(&self, b: &mut Builder) {
+        shapes::Rectangle::new(Rect::from_center_half_size(Vec2::ZERO, self.half_size))
+            .add_geometry(b);
+    }
+}
+
+impl Geometry for Rhombus {
+    fn add_geometry(&self, b: &mut Builder) {
+        let Vec2 { x, y } = self.half_diagonals;
+        add_polygon(
+            b,
+            &[
+                Vec2::new(x, 0.0),
+                Vec2::new(0.0, y),
+                Vec2::new(-x, 0.0),
+                Vec2::new(0.0, -y),
+            ],
+        );
+    }
+}
+
+impl Geometry for Triangle2d {
+    fn add_geometry(&self, b: &mut Builder) {
+        add_polygon(b, &self.vertices);
+    }
+}
+
+impl Geometry for RegularPolygon {
+    fn add_geometry(&self, b: &mut Builder) {
+        // Unlike `shapes::RegularPolygon`, the first vertex is at the top.
+        let vertices = self.vertices(0.0).into_iter().collect::<Vec<_>>();
+        add_polygon(b, &vertices);
+    }
+}
+
+impl Geometry for Polygon {
+    fn add_geometry(&self, b: &mut Builder) {
+        add_polygon(b, &self.vertices);
+    }
+}
+
+impl Geometry for ConvexPolygon {
+    fn add_geometry(&self, b: &mut Builder) {
+        add_polygon(b, self.vertices());
+    }
+}
+
+impl Geometry for Polyline2d {
+    fn add_geometry(&self, b: &mut Builder) {
+        shapes::Polygon {
+            points: self.vertices.clone(),
+            closed: false,
+        }
+        .add_geometry(b);
+    }
+}
+
+impl Geometry for Segment2d {
+    fn add_geometry(&self, b: &mut Builder) {
+        shapes::Line(self.vertices[0], self.vertices[1]).add_geometry(b);
+    }
+}
+
+impl Geometry for Capsule2d {
+    fn add_geometry(&self, b: &mut Builder) {
+        shapes::Capsule::vertical(Vec2::ZERO, self.half_length, self.radius).add_geometry(b);
+    }
+}
+
+/// Bevy's arcs are symmetric about the positive Y axis.
+impl Geometry for Arc2d {
+    fn add_geometry(&self, b: &mut Builder) {
+        shapes::Arc {
+            center: Vec2::ZERO,
+            radius: self.radius,
+            start_angle: FRAC_PI_2 - self.half_angle,
+            end_angle: FRAC_PI_2 + self.half_angle,
+        }
+        .add_geometry(b);
+    }
+}
+
+impl Geometry for CircularSector {
+    fn add_geometry(&self, b: &mut Builder) {
+        shapes::Sector {
+            center: Vec2::ZERO,
+            radius: self.arc.radius,
+            start_angle: FRAC_PI_2 - self.arc.half_angle,
+            end_angle: FRAC_PI_2 + self.arc.half_angle,
+        }
+        .add_geometry(b);
+    }
+}
+
+impl Geometry for CircularSegment {
+    fn add_geometry(&self, b: &mut Builder) {
+        let mut svg_builder = Builder::new().with_svg();
+        shapes::begin_arc(
+            &mut svg_builder,
+            Vec2::ZERO,
+            self.arc.radius,
+            FRAC_PI_2 - self.arc.half_angle,
+            2.0 * self.arc.half_angle,
+        );
+        svg_builder.close();
+        let path = svg_builder.build();
+        b.extend_from_paths(&[path.as_slice()]);
+    }
+}
+
+impl Geometry for Annulus {
+    fn add_geometry(&self, b: &mut Builder) {
+        Ring::new(self.outer_circle, self.inner_circle).add_geometry(b);
+    }
+}
+
+/// The inner shape is drawn in the opposite direction to the outer shape,
+/// so that it stays empty with both fill rules.
+impl<P: Primitive2d + Geometry> Geometry for Ring<P> {
+    fn add_geometry(&self, b: &mut Builder) {
+        let outer = build(&self.outer_shape);
+        let inner = build(&self.inner_shape);
+        b.extend_from_paths(&[outer.as_slice()]);
+        let tolerance = FillOptions::DEFAULT_TOLERANCE;
+        if (path_signed_area(&outer, tolerance) > 0.0)
+            == (path_signed_area(&inner, tolerance) > 0.0)
+        {
+            for event in inner.reversed() {
+                b.path_event(event);
+            }
+        } else {
+            b.extend_from_paths(&[inner.as_slice()]);
+        }
+    }
+}
+
+fn add_polygon(b: &mut Builder, vertices: &[Vec2]) {
+    shapes::Polygon {
+        points: vertices.to_vec(),
+        closed: true,
+    }
+    .add_geometry(b);
+}
+
+fn build(geometry: &impl Geometry) -> LyonPath {
+    let mut b = Builder::new();
+    geometry.add_geometry(&mut b);
+    b.build()
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::math::{
+        primitives::{Annulus, Rectangle, Ring},
+        Vec2,
+    };
+    use lyon_tessellation::FillRule;
+
+    use super::build;
+    use crate::{
+        entity::Path,
+        geometry::Geometry,
+        ops::{flatten, signed_area},
+    };
+
+    fn check_hole(ring: &impl Geometry) {
+        let path = build(ring);
+        let rings = flatten(&path, 0.01);
+        assert_eq!(rings.len(), 2);
+        assert!(signed_area(&rings[0].points) * signed_area(&rings[1].points) < 0.0);
+        let path = Path(path);
+        for fill_rule in [FillRule::EvenOdd, FillRule::NonZero] {
+            assert!(!path.contains_point(Vec2::ZERO, fill_rule, 0.01));
+            assert!(path.contains_point(Vec2::new(1.5, 0.0), fill_rule, 0.01));
+        }
+    }
+
+    #[test]
+    fn ring_hole() {
+        check_hole(&Annulus::new(1.0, 2.0));
+        check_hole(&Ring::new(Rectangle::new(4.0, 4.0), Rectangle::new(2.0, 2.0)));
+    }
+}
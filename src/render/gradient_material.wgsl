@@ -11,9 +11,15 @@ struct GradientMaterialUniform {
     end: vec4<f32>,
     start_pos: vec2<f32>,
     end_pos: vec2<f32>, 
+    flags: u32,
 }
 
+const GRADIENT_MATERIAL_FLAGS_STROKE_TEXTURE: u32 = 1u;
+const GRADIENT_MATERIAL_FLAGS_STROKE_TEXTURE_REPEAT: u32 = 2u;
+
 @group(2) @binding(0) var<uniform> material: GradientMaterialUniform;
+@group(2) @binding(1) var stroke_texture: texture_2d<f32>;
+@group(2) @binding(2) var stroke_sampler: sampler;
 
 fn invlerp_points(pos1: vec2<f32>, pos2: vec2<f32>, pos: vec2<f32>) -> f32 {
     let pointing_vec = pos2 - pos1;
@@ -103,6 +109,20 @@ fn fragment(
     var invlerp_progress = invlerp_points(material.start_pos, material.end_pos, mesh.local_position);
     var color = mix(material.start, material.end, invlerp_progress);
     var output_color: vec4<f32> = color;
+#ifdef VERTEX_UVS
+    // Derivatives are taken on the unwrapped coordinates so that repeating
+    // the texture does not produce seams.
+    let ddx = dpdx(mesh.uv);
+    let ddy = dpdy(mesh.uv);
+    var uv = vec2<f32>(clamp(mesh.uv.x, 0.0, 1.0), mesh.uv.y);
+    if (material.flags & GRADIENT_MATERIAL_FLAGS_STROKE_TEXTURE_REPEAT) != 0u {
+        uv.x = fract(mesh.uv.x);
+    }
+    let texture_color = textureSampleGrad(stroke_texture, stroke_sampler, uv, ddx, ddy);
+    if (material.flags & GRADIENT_MATERIAL_FLAGS_STROKE_TEXTURE) != 0u && mesh.uv.y >= 0.0 {
+        output_color = texture_color;
+    }
+#endif
     return output_color;
 }
 
@@ -3,6 +3,7 @@
 use bevy::{
     asset::{load_internal_asset, AssetApp, AssetId},
     color::LinearRgba,
+    image::Image,
     prelude::{App, Asset, Assets, Handle, Plugin, Shader, Vec2},
     reflect::prelude::*,
     render::render_resource::{AsBindGroup, ShaderType},
@@ -11,6 +12,11 @@ use bevy::{
 };
 use lyon_algorithms::geom::euclid::approxeq::ApproxEq;
 
+use crate::{
+    brush::Brush,
+    draw::{StrokeTexture, StrokeTextureMode},
+};
+
 /// Handle to the custom shader with a unique random ID
 pub const GRADIENT_MATERIAL_SHADER_HANDLE: Handle<Shader> =
     bevy::asset::uuid_handle!("00000000-0000-0000-0000-000000000001");
@@ -51,7 +57,8 @@ impl Material2d for GradientMaterial {
     }
 
     fn alpha_mode(&self) -> AlphaMode2d {
-        if self.uniform.start.alpha.approx_eq_eps(&1.0, &0.01)
+        if self.texture.is_none()
+            && self.uniform.start.alpha.approx_eq_eps(&1.0, &0.01)
             && self.uniform.end.alpha.approx_eq_eps(&1.0, &0.01)
         {
             AlphaMode2d::Opaque
@@ -67,6 +74,29 @@ impl Material2d for GradientMaterial {
 pub struct GradientMaterial {
     #[uniform(0)]
     pub(crate) uniform: GradientMaterialUniform,
+    #[texture(1)]
+    #[sampler(2)]
+    pub(crate) texture: Option<Handle<Image>>,
+}
+
+impl GradientMaterial {
+    /// Creates a material painting with `brush`, and with `texture` on the
+    /// vertices of a textured stroke.
+    pub(crate) fn new(brush: &Brush, texture: Option<&StrokeTexture>, offset: Vec2) -> Self {
+        let mut uniform = brush.clone_as_uniform();
+        uniform.start_pos += offset;
+        uniform.end_pos += offset;
+        if let Some(texture) = texture {
+            uniform.flags |= GradientMaterialUniform::STROKE_TEXTURE;
+            if let StrokeTextureMode::Tile(_) = texture.mode {
+                uniform.flags |= GradientMaterialUniform::STROKE_TEXTURE_REPEAT;
+            }
+        }
+        Self {
+            uniform,
+            texture: texture.map(|texture| texture.image.clone()),
+        }
+    }
 }
 
 #[derive(ShaderType, Reflect, Default, Debug, Clone, Copy)]
@@ -75,4 +105,14 @@ pub struct GradientMaterialUniform {
     pub end: LinearRgba,
     pub start_pos: Vec2,
     pub end_pos: Vec2,
+    /// Bit field of the `GradientMaterialUniform` flag constants.
+    pub flags: u32,
+}
+
+// NOTE: These must match the bit flags in gradient_material.wgsl!
+impl GradientMaterialUniform {
+    /// The stroke is drawn with the material texture.
+    pub const STROKE_TEXTURE: u32 = 1;
+    /// The stroke texture repeats along the path instead of being clamped.
+    pub const STROKE_TEXTURE_REPEAT: u32 = 2;
 }
@@ -3,26 +3,42 @@
 //! The structs defined in this module implement the
 //! [`Geometry`](crate::geometry::Geometry) trait. You can also implement
 //! the trait for your own shapes.
+//!
+//! The 2D primitives of [`bevy::math::primitives`], like `Circle` or
+//! `Capsule2d`, implement it as well.
 
 use bevy::{math::Vec2, prelude::Rect};
 use lyon_tessellation::{
     math::{point, Angle, Box2D, Point, Vector},
     path::{
-        builder::WithSvg, path::Builder, traits::SvgPathBuilder, ArcFlags, Polygon as LyonPolygon,
-        Winding,
+        builder::{BorderRadii, WithSvg},
+        path::{Builder, BuilderImpl}, traits::SvgPathBuilder, ArcFlags, Polygon as LyonPolygon,
     },
+    FillOptions,
 };
 use svgtypes::{PathParser, PathSegment};
 
 use crate::{
     geometry::Geometry,
+    ops::signed_area,
     utils::{ToPoint, ToVector},
 };
 
+#[cfg(feature = "text")]
+mod text;
+
+pub use lyon_tessellation::path::Winding;
+
+#[cfg(feature = "text")]
+pub use text::{OutlineFont, Text, TextAlignment};
+#[cfg(feature = "text")]
+pub use ttf_parser::FaceParsingError;
+
 /// Defines where the origin, or pivot of the `Rectangle` should be positioned.
 #[allow(missing_docs)]
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
 pub enum RectangleOrigin {
+    #[default]
     Center,
     BottomLeft,
     BottomRight,
@@ -31,24 +47,52 @@ pub enum RectangleOrigin {
     CustomCenter(Vec2),
 }
 
-impl Default for RectangleOrigin {
-    fn default() -> Self {
-        Self::Center
+impl RectangleOrigin {
+    /// Returns a rectangle of the given size, positioned so that this origin
+    /// lies at the local origin.
+    ///
+    /// For [`CustomCenter`](Self::CustomCenter), the rectangle is centered on
+    /// the given point instead.
+    #[must_use]
+    pub fn rect(self, extents: Vec2) -> Rect {
+        let min = match self {
+            Self::Center => -extents / 2.0,
+            Self::BottomLeft => Vec2::ZERO,
+            Self::BottomRight => Vec2::new(-extents.x, 0.0),
+            Self::TopRight => -extents,
+            Self::TopLeft => Vec2::new(0.0, -extents.y),
+            Self::CustomCenter(center) => center - extents / 2.0,
+        };
+        Rect::from_corners(min, min + extents)
     }
 }
 
 #[allow(missing_docs)]
 #[derive(Debug, Clone, Copy, PartialEq)]
-#[derive(Default)]
 pub struct Rectangle {
     pub rect: Rect,
+    /// The direction of the outline. [`Winding::Positive`], the default, is
+    /// counter-clockwise.
+    pub winding: Winding,
 }
 
 impl Rectangle {
-    #[must_use] pub fn new(rect: Rect) -> Self { Self { rect } }
+    #[must_use] pub fn new(rect: Rect) -> Self { Self { rect, winding: Winding::Positive } }
+
+    /// Creates a rectangle of the given size positioned relative to `origin`.
+    #[must_use]
+    pub fn from_extents(extents: Vec2, origin: RectangleOrigin) -> Self {
+        Self::new(origin.rect(extents))
+    }
 }
 
 
+impl Default for Rectangle {
+    fn default() -> Self {
+        Self::new(Rect::default())
+    }
+}
+
 impl Geometry for Rectangle {
     fn add_geometry(&self, b: &mut Builder) {
         b.add_rectangle(
@@ -56,16 +100,137 @@ impl Geometry for Rectangle {
                 min: self.rect.min.to_point(),
                 max: self.rect.max.to_point(),
             },
-            Winding::Positive,
+            self.winding,
         );
     }
 }
 
+/// The radius of each corner of a [`RoundedRectangle`].
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct CornerRadii {
+    pub top_left: f32,
+    pub top_right: f32,
+    pub bottom_right: f32,
+    pub bottom_left: f32,
+}
+
+impl CornerRadii {
+    /// Returns radii where all the corners have the same `radius`.
+    #[must_use]
+    pub fn all(radius: f32) -> Self {
+        Self {
+            top_left: radius,
+            top_right: radius,
+            bottom_right: radius,
+            bottom_left: radius,
+        }
+    }
+}
+
+impl From<f32> for CornerRadii {
+    fn from(radius: f32) -> Self {
+        Self::all(radius)
+    }
+}
+
+/// A rectangle with rounded corners.
+///
+/// Radii that do not fit are clamped, as lyon does: negative radii are made
+/// positive, and each radius is limited to the shorter side of the
+/// rectangle. Then, when the radii at both ends of a side add up to more than
+/// its length, half of the excess is subtracted from each of them. Unlike
+/// scaling, this keeps the difference between unequal radii.
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RoundedRectangle {
+    pub rect: Rect,
+    pub radii: CornerRadii,
+    /// The direction of the outline. [`Winding::Positive`], the default, is
+    /// counter-clockwise.
+    pub winding: Winding,
+}
+
+impl RoundedRectangle {
+    /// Creates a rounded rectangle covering `rect`.
+    #[must_use]
+    pub fn new(rect: Rect, radii: impl Into<CornerRadii>) -> Self {
+        Self {
+            rect,
+            radii: radii.into(),
+            winding: Winding::Positive,
+        }
+    }
+
+    /// Creates a rounded rectangle of the given size positioned relative to
+    /// `origin`.
+    #[must_use]
+    pub fn from_extents(
+        extents: Vec2,
+        origin: RectangleOrigin,
+        radii: impl Into<CornerRadii>,
+    ) -> Self {
+        Self::new(origin.rect(extents), radii)
+    }
+}
+
+impl Default for RoundedRectangle {
+    fn default() -> Self {
+        Self::new(Rect::default(), CornerRadii::default())
+    }
+}
+
+impl Geometry for RoundedRectangle {
+    fn add_geometry(&self, b: &mut Builder) {
+        // Lyon expects a y-down coordinate system, so top and bottom are swapped.
+        b.add_rounded_rectangle(
+            &Box2D {
+                min: self.rect.min.to_point(),
+                max: self.rect.max.to_point(),
+            },
+            &BorderRadii {
+                top_left: self.radii.bottom_left,
+                top_right: self.radii.bottom_right,
+                bottom_left: self.radii.top_left,
+                bottom_right: self.radii.top_right,
+            },
+            self.winding,
+        );
+    }
+}
+
+/// A circle.
+///
+/// # Example
+///
+/// A circle going clockwise cuts a hole in a rectangle going
+/// counter-clockwise, with both fill rules:
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_prototype_lyon::prelude::*;
+/// #
+/// let plate = GeometryBuilder::new()
+///     .add(&shapes::Rectangle::new(Rect::new(-2.0, -2.0, 2.0, 2.0)))
+///     .add(&shapes::Circle {
+///         radius: 1.0,
+///         winding: Winding::Negative,
+///         ..default()
+///     })
+///     .build();
+///
+/// assert!(!plate.contains_point(Vec2::ZERO, FillRule::NonZero, 0.1));
+/// assert!(!plate.contains_point(Vec2::ZERO, FillRule::EvenOdd, 0.1));
+/// assert!(plate.contains_point(Vec2::new(1.5, 0.0), FillRule::NonZero, 0.1));
+/// ```
 #[allow(missing_docs)]
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Circle {
     pub radius: f32,
     pub center: Vec2,
+    /// The direction of the outline. [`Winding::Positive`], the default, is
+    /// counter-clockwise.
+    pub winding: Winding,
 }
 
 impl Default for Circle {
@@ -73,13 +238,14 @@ impl Default for Circle {
         Self {
             radius: 1.0,
             center: Vec2::ZERO,
+            winding: Winding::Positive,
         }
     }
 }
 
 impl Geometry for Circle {
     fn add_geometry(&self, b: &mut Builder) {
-        b.add_circle(self.center.to_point(), self.radius, Winding::Positive);
+        b.add_circle(self.center.to_point(), self.radius, self.winding);
     }
 }
 
@@ -88,6 +254,9 @@ impl Geometry for Circle {
 pub struct Ellipse {
     pub radii: Vec2,
     pub center: Vec2,
+    /// The direction of the outline. [`Winding::Positive`], the default, is
+    /// counter-clockwise.
+    pub winding: Winding,
 }
 
 impl Default for Ellipse {
@@ -95,6 +264,7 @@ impl Default for Ellipse {
         Self {
             radii: Vec2::ONE,
             center: Vec2::ZERO,
+            winding: Winding::Positive,
         }
     }
 }
@@ -105,7 +275,7 @@ impl Geometry for Ellipse {
             self.center.to_point(),
             self.radii.to_vector(),
             Angle::zero(),
-            Winding::Positive,
+            self.winding,
         );
     }
 }
@@ -180,6 +350,67 @@ impl Geometry for RoundedPolygon {
     }
 }
 
+/// A polygon with holes, such as a window frame or a region of a map.
+///
+/// The outer ring is drawn counter-clockwise and the holes clockwise,
+/// whatever the order of their points, so that the holes are left empty with
+/// both [`FillRule`](lyon_tessellation::FillRule)s. Holes should be inside the
+/// outer ring and should not overlap each other.
+///
+/// # Example
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_prototype_lyon::prelude::*;
+/// #
+/// let frame = shapes::PolygonWithHoles {
+///     outer: vec![
+///         Vec2::new(-2.0, -2.0),
+///         Vec2::new(2.0, -2.0),
+///         Vec2::new(2.0, 2.0),
+///         Vec2::new(-2.0, 2.0),
+///     ],
+///     holes: vec![vec![
+///         Vec2::new(-1.0, -1.0),
+///         Vec2::new(1.0, -1.0),
+///         Vec2::new(1.0, 1.0),
+///         Vec2::new(-1.0, 1.0),
+///     ]],
+/// };
+/// let path = GeometryBuilder::build_as(&frame);
+///
+/// assert_eq!(path.area(FillRule::NonZero), 12.0);
+/// assert_eq!(path.area(FillRule::EvenOdd), 12.0);
+/// ```
+#[allow(missing_docs)]
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct PolygonWithHoles {
+    pub outer: Vec<Vec2>,
+    pub holes: Vec<Vec<Vec2>>,
+}
+
+impl Geometry for PolygonWithHoles {
+    fn add_geometry(&self, b: &mut Builder) {
+        add_ring(b, &self.outer, true);
+        for hole in &self.holes {
+            add_ring(b, hole, false);
+        }
+    }
+}
+
+/// Adds a closed ring, reversed if needed to be counter-clockwise or
+/// clockwise.
+fn add_ring(b: &mut Builder, ring: &[Vec2], counter_clockwise: bool) {
+    let mut points = ring.iter().map(|p| p.to_point()).collect::<Vec<Point>>();
+    if (signed_area(ring) > 0.0) != counter_clockwise {
+        points.reverse();
+    }
+    b.add_polygon(LyonPolygon {
+        points: points.as_slice(),
+        closed: true,
+    });
+}
+
 /// The regular polygon feature used to determine the dimensions of the polygon.
 #[allow(missing_docs)]
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -198,6 +429,9 @@ pub struct RegularPolygon {
     pub sides: usize,
     pub center: Vec2,
     pub feature: RegularPolygonFeature,
+    /// The direction of the outline. [`Winding::Positive`], the default, is
+    /// counter-clockwise.
+    pub winding: Winding,
 }
 
 impl RegularPolygon {
@@ -219,6 +453,7 @@ impl Default for RegularPolygon {
             sides: 3,
             center: Vec2::ZERO,
             feature: RegularPolygonFeature::Radius(1.0),
+            winding: Winding::Positive,
         }
     }
 }
@@ -245,6 +480,9 @@ impl Geometry for RegularPolygon {
             let y = radius.mul_add(cur_angle.sin(), self.center.y);
             points.push(point(x, y));
         }
+        if self.winding == Winding::Negative {
+            points.reverse();
+        }
 
         let polygon = LyonPolygon {
             points: points.as_slice(),
@@ -255,6 +493,519 @@ impl Geometry for RegularPolygon {
     }
 }
 
+/// A star with `points` tips alternating between two radii.
+///
+/// With no rotation, the first tip points up.
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Star {
+    pub points: usize,
+    pub center: Vec2,
+    /// The radius of the circle passing through the tips.
+    pub outer_radius: f32,
+    /// The radius of the circle passing through the inner corners.
+    pub inner_radius: f32,
+    /// Counter-clockwise rotation, in radians.
+    pub rotation: f32,
+    /// The radius used to round every corner. Zero keeps them sharp.
+    pub corner_radius: f32,
+}
+
+impl Default for Star {
+    fn default() -> Self {
+        Self {
+            points: 5,
+            center: Vec2::ZERO,
+            outer_radius: 1.0,
+            inner_radius: 0.5,
+            rotation: 0.0,
+            corner_radius: 0.0,
+        }
+    }
+}
+
+impl Geometry for Star {
+    fn add_geometry(&self, b: &mut Builder) {
+        use std::f32::consts::{FRAC_PI_2, PI};
+        assert!(self.points > 1, "Stars must have at least 2 points");
+        let step = PI / self.points as f32;
+        let offset = FRAC_PI_2 + self.rotation;
+
+        let points = (0..self.points * 2)
+            .map(|i| {
+                let radius = if i % 2 == 0 {
+                    self.outer_radius
+                } else {
+                    self.inner_radius
+                };
+                let angle = (i as f32).mul_add(step, offset);
+                (self.center + Vec2::from_angle(angle) * radius).to_point()
+            })
+            .collect::<Vec<Point>>();
+        let polygon = LyonPolygon {
+            points: points.as_slice(),
+            closed: true,
+        };
+
+        if self.corner_radius > 0.0 {
+            lyon_algorithms::rounded_polygon::add_rounded_polygon(
+                b,
+                polygon,
+                self.corner_radius,
+                lyon_algorithms::path::NO_ATTRIBUTES,
+            );
+        } else {
+            b.add_polygon(polygon);
+        }
+    }
+}
+
+/// Starts a new sub-path on the circle around `center` at `start_angle` and
+/// adds an arc sweeping `sweep_angle` radians from there.
+pub(crate) fn begin_arc(
+    b: &mut WithSvg<BuilderImpl>,
+    center: Vec2,
+    radius: f32,
+    start_angle: f32,
+    sweep_angle: f32,
+) {
+    b.move_to((center + Vec2::from_angle(start_angle) * radius).to_point());
+    add_arc(b, center, radius, sweep_angle);
+}
+
+/// Adds an arc sweeping `sweep_angle` radians around `center`, from the current
+/// position.
+///
+/// Sweeps over a full turn are reduced to a full turn, which lyon draws as a
+/// whole circle.
+///
+/// # Panics
+///
+/// Panics if `sweep_angle` is not finite.
+fn add_arc(b: &mut WithSvg<BuilderImpl>, center: Vec2, radius: f32, sweep_angle: f32) {
+    use std::f32::consts::TAU;
+    assert!(sweep_angle.is_finite(), "Arc angles must be finite");
+    b.arc(
+        center.to_point(),
+        Vector::splat(radius),
+        Angle::radians(sweep_angle.clamp(-TAU, TAU)),
+        Angle::zero(),
+    );
+}
+
+/// An open circular arc, to be stroked.
+///
+/// Angles are in radians, counter-clockwise from the positive X axis. The arc
+/// goes clockwise when `end_angle` is less than `start_angle`. Angles a turn
+/// or more apart draw the whole circle, closed.
+///
+/// # Panics
+///
+/// Panics if the angles are not finite.
+///
+/// ```
+/// # use bevy_prototype_lyon::prelude::*;
+/// # use std::f32::consts::PI;
+/// // One and a half turns draw the circle once.
+/// let arc = shapes::Arc {
+///     radius: 100.0,
+///     start_angle: 0.0,
+///     end_angle: 3.0 * PI,
+///     ..shapes::Arc::default()
+/// };
+/// let path = GeometryBuilder::build_as(&arc);
+/// assert!((path.length() - 200.0 * PI).abs() < 2.0 * PI);
+///
+/// // Huge sweeps too.
+/// let path = GeometryBuilder::build_as(&shapes::Arc { end_angle: 1e30, ..arc });
+/// assert!((path.length() - 200.0 * PI).abs() < 2.0 * PI);
+/// ```
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Arc {
+    pub center: Vec2,
+    pub radius: f32,
+    pub start_angle: f32,
+    pub end_angle: f32,
+}
+
+impl Default for Arc {
+    fn default() -> Self {
+        Self {
+            center: Vec2::ZERO,
+            radius: 1.0,
+            start_angle: 0.0,
+            end_angle: std::f32::consts::PI,
+        }
+    }
+}
+
+impl Geometry for Arc {
+    fn add_geometry(&self, b: &mut Builder) {
+        let mut svg_builder = Builder::new().with_svg();
+        let sweep_angle = self.end_angle - self.start_angle;
+        begin_arc(
+            &mut svg_builder,
+            self.center,
+            self.radius,
+            self.start_angle,
+            sweep_angle,
+        );
+        if sweep_angle.abs() >= std::f32::consts::TAU {
+            svg_builder.close();
+        }
+        let path = svg_builder.build();
+        b.extend_from_paths(&[path.as_slice()]);
+    }
+}
+
+/// A pie slice: the region between two radii of a circle and the arc joining
+/// them.
+///
+/// Angles follow the same convention as [`Arc`].
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sector {
+    pub center: Vec2,
+    pub radius: f32,
+    pub start_angle: f32,
+    pub end_angle: f32,
+}
+
+impl Default for Sector {
+    fn default() -> Self {
+        Self {
+            center: Vec2::ZERO,
+            radius: 1.0,
+            start_angle: 0.0,
+            end_angle: std::f32::consts::FRAC_PI_2,
+        }
+    }
+}
+
+impl Geometry for Sector {
+    fn add_geometry(&self, b: &mut Builder) {
+        let mut svg_builder = Builder::new().with_svg();
+        svg_builder.move_to(self.center.to_point());
+        svg_builder.line_to(
+            (self.center + Vec2::from_angle(self.start_angle) * self.radius).to_point(),
+        );
+        add_arc(
+            &mut svg_builder,
+            self.center,
+            self.radius,
+            self.end_angle - self.start_angle,
+        );
+        svg_builder.close();
+        let path = svg_builder.build();
+        b.extend_from_paths(&[path.as_slice()]);
+    }
+}
+
+/// A segment of a ring: the region between two concentric arcs.
+///
+/// Angles follow the same convention as [`Arc`]. A full turn draws the whole
+/// ring as a single closed outline.
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AnnularSector {
+    pub center: Vec2,
+    pub inner_radius: f32,
+    pub outer_radius: f32,
+    pub start_angle: f32,
+    pub end_angle: f32,
+}
+
+impl Default for AnnularSector {
+    fn default() -> Self {
+        Self {
+            center: Vec2::ZERO,
+            inner_radius: 0.5,
+            outer_radius: 1.0,
+            start_angle: 0.0,
+            end_angle: std::f32::consts::FRAC_PI_2,
+        }
+    }
+}
+
+impl Geometry for AnnularSector {
+    fn add_geometry(&self, b: &mut Builder) {
+        let sweep_angle = self.end_angle - self.start_angle;
+        let mut svg_builder = Builder::new().with_svg();
+        begin_arc(
+            &mut svg_builder,
+            self.center,
+            self.outer_radius,
+            self.start_angle,
+            sweep_angle,
+        );
+        svg_builder.line_to(
+            (self.center + Vec2::from_angle(self.end_angle) * self.inner_radius).to_point(),
+        );
+        add_arc(&mut svg_builder, self.center, self.inner_radius, -sweep_angle);
+        svg_builder.close();
+        let path = svg_builder.build();
+        b.extend_from_paths(&[path.as_slice()]);
+    }
+}
+
+/// A capsule, also called stadium or pill: two semicircles joined by the
+/// sides of a rectangle.
+///
+/// The semicircles are centered on `start` and `end`.
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Capsule {
+    pub start: Vec2,
+    pub end: Vec2,
+    pub radius: f32,
+}
+
+impl Capsule {
+    /// Creates a capsule whose semicircles are centered on `start` and `end`.
+    #[must_use]
+    pub fn new(start: Vec2, end: Vec2, radius: f32) -> Self {
+        Self { start, end, radius }
+    }
+
+    /// Creates a horizontal capsule. `half_length` is half the distance
+    /// between the centers of the semicircles.
+    #[must_use]
+    pub fn horizontal(center: Vec2, half_length: f32, radius: f32) -> Self {
+        let offset = Vec2::new(half_length, 0.0);
+        Self::new(center - offset, center + offset, radius)
+    }
+
+    /// Creates a vertical capsule, with the same dimensions as Bevy's
+    /// [`Capsule2d`](bevy::math::primitives::Capsule2d).
+    #[must_use]
+    pub fn vertical(center: Vec2, half_length: f32, radius: f32) -> Self {
+        let offset = Vec2::new(0.0, half_length);
+        Self::new(center - offset, center + offset, radius)
+    }
+}
+
+impl Default for Capsule {
+    fn default() -> Self {
+        Self::horizontal(Vec2::ZERO, 0.5, 0.5)
+    }
+}
+
+impl Geometry for Capsule {
+    fn add_geometry(&self, b: &mut Builder) {
+        let Some(direction) = (self.end - self.start).try_normalize() else {
+            b.add_circle(self.start.to_point(), self.radius, Winding::Positive);
+            return;
+        };
+        let right = Vec2::new(direction.y, -direction.x) * self.radius;
+
+        let mut svg_builder = Builder::new().with_svg();
+        svg_builder.move_to((self.start + right).to_point());
+        svg_builder.line_to((self.end + right).to_point());
+        add_arc(&mut svg_builder, self.end, self.radius, std::f32::consts::PI);
+        svg_builder.line_to((self.start - right).to_point());
+        add_arc(&mut svg_builder, self.start, self.radius, std::f32::consts::PI);
+        svg_builder.close();
+        let path = svg_builder.build();
+        b.extend_from_paths(&[path.as_slice()]);
+    }
+}
+
+/// A smooth curve passing through all of its `points`.
+///
+/// The curve is a Catmull-Rom spline made of cubic beziers:
+/// - `alpha` selects the parameterization: `0.0` is uniform, `0.5` is
+///   centripetal, which avoids cusps and self-intersections, and `1.0` is
+///   chordal.
+/// - `tension` shortens the tangents: `0.0` is a regular Catmull-Rom spline,
+///   `1.0` gives straight lines, other values give cardinal splines.
+#[allow(missing_docs)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Spline {
+    pub points: Vec<Vec2>,
+    pub closed: bool,
+    pub alpha: f32,
+    pub tension: f32,
+}
+
+impl Default for Spline {
+    fn default() -> Self {
+        Self {
+            points: Vec::new(),
+            closed: false,
+            alpha: 0.5,
+            tension: 0.0,
+        }
+    }
+}
+
+impl Geometry for Spline {
+    fn add_geometry(&self, b: &mut Builder) {
+        let Some(first) = self.points.first() else {
+            return;
+        };
+        b.begin(first.to_point());
+        for [ctrl1, ctrl2, to] in spline_segments(&self.points, self.closed, self.alpha, self.tension)
+        {
+            b.cubic_bezier_to(ctrl1.to_point(), ctrl2.to_point(), to.to_point());
+        }
+        b.end(self.closed);
+    }
+}
+
+/// Returns the control points and end point of each cubic bezier of a
+/// Catmull-Rom spline passing through `points`. See [`Spline`].
+pub(crate) fn spline_segments(
+    points: &[Vec2],
+    closed: bool,
+    alpha: f32,
+    tension: f32,
+) -> Vec<[Vec2; 3]> {
+    let n = points.len();
+    if n < 2 {
+        return Vec::new();
+    }
+    let segments = if closed { n } else { n - 1 };
+
+    (0..segments)
+        .map(|i| {
+            let p1 = points[i];
+            let p2 = points[(i + 1) % n];
+            // Open splines extend their ends by mirroring the neighbouring point.
+            let p0 = match i {
+                0 if closed => points[n - 1],
+                0 => 2.0 * p1 - p2,
+                _ => points[i - 1],
+            };
+            let p3 = if i + 2 < n || closed {
+                points[(i + 2) % n]
+            } else {
+                2.0 * p2 - p1
+            };
+            let d1 = p0.distance(p1).powf(alpha);
+            let d2 = p1.distance(p2).powf(alpha);
+            let d3 = p2.distance(p3).powf(alpha);
+
+            let mut ctrl1 = p1;
+            let mut ctrl2 = p2;
+            if d2 > f32::EPSILON {
+                if d1 > f32::EPSILON {
+                    ctrl1 = (d1 * d1 * p2 - d2 * d2 * p0
+                        + (2.0 * d1 * d1 + 3.0 * d1 * d2 + d2 * d2) * p1)
+                        / (3.0 * d1 * (d1 + d2));
+                }
+                if d3 > f32::EPSILON {
+                    ctrl2 = (d3 * d3 * p1 - d2 * d2 * p3
+                        + (2.0 * d3 * d3 + 3.0 * d3 * d2 + d2 * d2) * p2)
+                        / (3.0 * d3 * (d3 + d2));
+                }
+            }
+            [
+                p1.lerp(ctrl1, 1.0 - tension),
+                p2.lerp(ctrl2, 1.0 - tension),
+                p2,
+            ]
+        })
+        .collect()
+}
+
+/// A superellipse, also called Lamé curve: the points where
+/// `|x / radii.x|^exponent + |y / radii.y|^exponent = 1`.
+///
+/// An exponent of `2.0` gives an ellipse, higher values approach a rectangle,
+/// and `4.0` gives the squircle used by app icons. Values below `1.0` give
+/// star-like concave shapes.
+///
+/// The curve is approximated with cubic beziers passing through
+/// [`samples`](Self::samples) points, more of them as `tolerance` shrinks.
+///
+/// ```
+/// # use bevy::math::Vec2;
+/// # use bevy_prototype_lyon::prelude::*;
+/// # use std::f32::consts::PI;
+/// // An exponent of 2 gives a circle.
+/// let circle = shapes::Superellipse {
+///     radii: Vec2::splat(1000.0),
+///     exponent: 2.0,
+///     ..shapes::Superellipse::default()
+/// };
+/// let path = GeometryBuilder::build_as(&circle);
+/// let area = path.area(FillRule::NonZero);
+/// // The area is off by at most the perimeter times the tolerance.
+/// assert!((area - PI * 1000.0 * 1000.0).abs() < 2.0 * PI * 1000.0 * circle.tolerance);
+/// assert!(circle.samples() > 64);
+/// ```
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Superellipse {
+    pub center: Vec2,
+    pub radii: Vec2,
+    pub exponent: f32,
+    /// The distance between the curve and the beziers drawing it that the
+    /// number of samples aims for. See [`samples`](Self::samples).
+    pub tolerance: f32,
+}
+
+impl Superellipse {
+    /// Returns the number of points of the curve joined by beziers: the
+    /// number of sides of a regular polygon staying within `tolerance` of a
+    /// circle of the larger radius, and at least 8.
+    ///
+    /// This is a heuristic, not a bound on the error: the sharp corners of
+    /// large exponents and the cusps of exponents below 1 can stray further
+    /// from the curve.
+    #[must_use]
+    pub fn samples(&self) -> usize {
+        use std::f32::consts::TAU;
+        let radius = self.radii.abs().max_element();
+        let step = 2.0 * (1.0 - self.tolerance / radius).clamp(-1.0, 1.0).acos();
+        // A multiple of 4 keeps the curve symmetric about both axes.
+        #[allow(clippy::cast_sign_loss)] // The step is positive, or NaN which casts to 0.
+        let samples = (TAU / step).ceil().min(4096.0) as usize;
+        samples.next_multiple_of(4).max(8)
+    }
+}
+
+impl Default for Superellipse {
+    fn default() -> Self {
+        Self {
+            center: Vec2::ZERO,
+            radii: Vec2::ONE,
+            exponent: 4.0,
+            tolerance: FillOptions::DEFAULT_TOLERANCE,
+        }
+    }
+}
+
+impl Geometry for Superellipse {
+    fn add_geometry(&self, b: &mut Builder) {
+        use std::f32::consts::TAU;
+        assert!(
+            self.exponent > 0.0,
+            "Superellipses must have a positive exponent"
+        );
+        let power = 2.0 / self.exponent;
+        let samples = self.samples();
+        let points = (0..samples)
+            .map(|i| {
+                let (sin, cos) = (TAU * i as f32 / samples as f32).sin_cos();
+                let unit = Vec2::new(
+                    cos.signum() * cos.abs().powf(power),
+                    sin.signum() * sin.abs().powf(power),
+                );
+                self.center + unit * self.radii
+            })
+            .collect();
+
+        Spline {
+            points,
+            closed: true,
+            ..Spline::default()
+        }
+        .add_geometry(b);
+    }
+}
+
 /// A simple line segment, specified by two points.
 #[allow(missing_docs)]
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -281,10 +1032,10 @@ impl Geometry for Line {
 /// 1) Go to File>Document Properties>General>Display Units and set it to px
 ///
 /// 2) In File>Document Properties>Custom Size>Units set it to px, also, this
-/// size would be used for `svg_doc_size_in_px`
+///    size would be used for `svg_doc_size_in_px`
 ///
 /// 3) In File>Document Properties>Scale>Scale x make sure it is set to 1 User
-/// unit per px
+///    unit per px
 ///
 ///Example exists in the examples folder
 pub struct SvgPathShape {
@@ -297,7 +1048,7 @@ pub struct SvgPathShape {
     pub svg_path_string: String,
 }
 fn get_y_in_bevy_orientation(y: f64) -> f32 {
-    y as f32 * -1.
+    -(y as f32)
 }
 fn get_y_after_offset(y: f64, offset_y: f32) -> f32 {
     get_y_in_bevy_orientation(y) + offset_y
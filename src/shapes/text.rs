@@ -0,0 +1,308 @@
+//! Text outlines built from font files.
+
+use std::{fmt, sync::Arc};
+
+use bevy::{math::Vec2, text::Font};
+use lyon_tessellation::path::path::Builder;
+use ttf_parser::{Face, FaceParsingError, GlyphId, OutlineBuilder};
+
+use crate::{geometry::Geometry, utils::ToPoint};
+
+/// A TrueType or OpenType font used to build [`Text`] outlines.
+///
+/// Fonts are equal when they share the same data and face index, so comparing
+/// them does not compare the whole font files.
+#[derive(Clone)]
+pub struct OutlineFont {
+    data: Arc<Vec<u8>>,
+    index: u32,
+}
+
+impl OutlineFont {
+    /// Parses the first font face contained in `data`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `data` is not a valid font file.
+    pub fn from_bytes(data: Vec<u8>) -> Result<Self, FaceParsingError> {
+        Self::from_shared(Arc::new(data), 0)
+    }
+
+    /// Parses the face at `index` in `data`, which may be a font collection
+    /// like a `.ttc` file. Single fonts only have the face `0`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `data` is not a valid font file or has no face at
+    /// `index`.
+    pub fn from_collection(data: Vec<u8>, index: u32) -> Result<Self, FaceParsingError> {
+        Self::from_shared(Arc::new(data), index)
+    }
+
+    /// Shares the data of a Bevy [`Font`] asset.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the font cannot be parsed.
+    pub fn from_font(font: &Font) -> Result<Self, FaceParsingError> {
+        Self::from_shared(font.data.clone(), 0)
+    }
+
+    /// Returns the index of the face in the font data.
+    #[must_use]
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    fn from_shared(data: Arc<Vec<u8>>, index: u32) -> Result<Self, FaceParsingError> {
+        Face::parse(&data, index)?;
+        Ok(Self { data, index })
+    }
+
+    fn face(&self) -> Face<'_> {
+        Face::parse(&self.data, self.index).expect("font data is validated on creation")
+    }
+}
+
+/// Shows the size of the font data rather than the whole file.
+impl fmt::Debug for OutlineFont {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OutlineFont")
+            .field("data_len", &self.data.len())
+            .field("index", &self.index)
+            .finish()
+    }
+}
+
+impl PartialEq for OutlineFont {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.data, &other.data) && self.index == other.index
+    }
+}
+
+impl Eq for OutlineFont {}
+
+/// Horizontal alignment of the lines of a [`Text`].
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TextAlignment {
+    #[default]
+    Left,
+    Center,
+    Right,
+}
+
+/// The outlines of the glyphs of a string, laid out with a font.
+///
+/// `position` is the start of the baseline of the first line; lines are
+/// separated by newlines and aligned around it. Kerning is read from the
+/// `kern` table of the font; shaping and `GPOS` kerning are not supported.
+#[allow(missing_docs)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Text {
+    pub font: OutlineFont,
+    pub text: String,
+    /// The size of the em square, in pixels.
+    pub font_size: f32,
+    pub position: Vec2,
+    pub alignment: TextAlignment,
+    /// Multiplier applied to the line height defined by the font.
+    pub line_spacing: f32,
+}
+
+impl Text {
+    /// Creates left-aligned text starting at the origin.
+    #[must_use]
+    pub fn new(font: OutlineFont, text: impl Into<String>, font_size: f32) -> Self {
+        Self {
+            font,
+            text: text.into(),
+            font_size,
+            position: Vec2::ZERO,
+            alignment: TextAlignment::default(),
+            line_spacing: 1.0,
+        }
+    }
+}
+
+impl Geometry for Text {
+    fn add_geometry(&self, b: &mut Builder) {
+        let face = self.font.face();
+        let scale = self.font_size / f32::from(face.units_per_em());
+        let line_height = f32::from(face.ascender()) - f32::from(face.descender())
+            + f32::from(face.line_gap());
+
+        for (i, line) in self.text.lines().enumerate() {
+            let glyphs = line
+                .chars()
+                .map(|c| face.glyph_index(c).unwrap_or_default())
+                .collect::<Vec<_>>();
+            let (offsets, width) = layout_line(&face, &glyphs);
+            let start = match self.alignment {
+                TextAlignment::Left => 0.0,
+                TextAlignment::Center => -width / 2.0,
+                TextAlignment::Right => -width,
+            };
+            let baseline = -(i as f32) * line_height * self.line_spacing;
+
+            for (glyph, offset) in glyphs.into_iter().zip(offsets) {
+                let mut outline = GlyphOutline {
+                    b: &mut *b,
+                    origin: self.position + Vec2::new(start + offset, baseline) * scale,
+                    scale,
+                    open: false,
+                };
+                face.outline_glyph(glyph, &mut outline);
+                outline.end();
+            }
+        }
+    }
+}
+
+/// Returns the horizontal offset of each glyph and the width of the line, in
+/// font units.
+fn layout_line(face: &Face, glyphs: &[GlyphId]) -> (Vec<f32>, f32) {
+    let mut offsets = Vec::with_capacity(glyphs.len());
+    let mut x = 0.0;
+    for (i, &glyph) in glyphs.iter().enumerate() {
+        if i > 0 {
+            x += kerning(face, glyphs[i - 1], glyph);
+        }
+        offsets.push(x);
+        x += f32::from(face.glyph_hor_advance(glyph).unwrap_or(0));
+    }
+    (offsets, x)
+}
+
+fn kerning(face: &Face, left: GlyphId, right: GlyphId) -> f32 {
+    face.tables()
+        .kern
+        .into_iter()
+        .flat_map(|kern| kern.subtables)
+        .filter(|subtable| subtable.horizontal && !subtable.variable)
+        .find_map(|subtable| subtable.glyphs_kerning(left, right))
+        .map_or(0.0, f32::from)
+}
+
+/// Writes a glyph outline, in font units, into a lyon path builder.
+struct GlyphOutline<'a> {
+    b: &'a mut Builder,
+    origin: Vec2,
+    scale: f32,
+    open: bool,
+}
+
+impl GlyphOutline<'_> {
+    fn point(&self, x: f32, y: f32) -> lyon_tessellation::math::Point {
+        (self.origin + Vec2::new(x, y) * self.scale).to_point()
+    }
+
+    fn end(&mut self) {
+        if self.open {
+            self.b.end(false);
+            self.open = false;
+        }
+    }
+}
+
+impl OutlineBuilder for GlyphOutline<'_> {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.end();
+        let at = self.point(x, y);
+        self.b.begin(at);
+        self.open = true;
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        let to = self.point(x, y);
+        self.b.line_to(to);
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        let (ctrl, to) = (self.point(x1, y1), self.point(x, y));
+        self.b.quadratic_bezier_to(ctrl, to);
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        let (ctrl1, ctrl2, to) = (self.point(x1, y1), self.point(x2, y2), self.point(x, y));
+        self.b.cubic_bezier_to(ctrl1, ctrl2, to);
+    }
+
+    fn close(&mut self) {
+        if self.open {
+            self.b.end(true);
+            self.open = false;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::math::Rect;
+
+    use super::{OutlineFont, Text, TextAlignment};
+    use crate::{entity::Path, geometry::GeometryBuilder};
+
+    /// A font with 1000 units per em, ascender 800 and descender -200. `A` and
+    /// `V` are squares spanning 50..550 horizontally and 0..500 vertically,
+    /// with an advance of 600, and `V` is kerned by -200 after `A`. The
+    /// `.notdef` glyph spans 100..500 and 0..700.
+    fn font() -> OutlineFont {
+        OutlineFont::from_bytes(include_bytes!("../../assets/fonts/squares.ttf").to_vec())
+            .unwrap()
+    }
+
+    /// Builds `text` one font unit per pixel.
+    fn build(text: &str, alignment: TextAlignment) -> Path {
+        GeometryBuilder::build_as(&Text {
+            alignment,
+            ..Text::new(font(), text, 1000.0)
+        })
+    }
+
+    #[test]
+    fn line_breaks() {
+        let path = build("A\nAA", TextAlignment::Left);
+        assert_eq!(path.aabb(), Rect::new(50.0, -1000.0, 1150.0, 500.0));
+    }
+
+    #[test]
+    fn alignment() {
+        for (alignment, expected) in [
+            (TextAlignment::Left, Rect::new(50.0, 0.0, 1150.0, 500.0)),
+            (TextAlignment::Center, Rect::new(-550.0, 0.0, 550.0, 500.0)),
+            (TextAlignment::Right, Rect::new(-1150.0, 0.0, -50.0, 500.0)),
+        ] {
+            assert_eq!(build("AA", alignment).aabb(), expected, "{alignment:?}");
+        }
+    }
+
+    #[test]
+    fn kerning() {
+        assert_eq!(
+            build("AV", TextAlignment::Left).aabb(),
+            Rect::new(50.0, 0.0, 950.0, 500.0)
+        );
+        assert_eq!(
+            build("VA", TextAlignment::Left).aabb(),
+            Rect::new(50.0, 0.0, 1150.0, 500.0)
+        );
+    }
+
+    #[test]
+    fn missing_glyphs() {
+        let path = build("?", TextAlignment::Left);
+        assert_eq!(path.aabb(), Rect::new(100.0, 0.0, 500.0, 700.0));
+    }
+
+    #[test]
+    fn debug() {
+        let font = font();
+        assert_eq!(
+            format!("{font:?}"),
+            format!("OutlineFont {{ data_len: {}, index: 0 }}", font.data.len())
+        );
+        assert_eq!(font, font.clone());
+        assert_ne!(font, self::font());
+    }
+}
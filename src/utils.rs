@@ -22,3 +22,19 @@ impl ToVector for Vec2 {
         Vector::new(self.x, self.y)
     }
 }
+
+pub trait ToVec2 {
+    fn to_vec2(self) -> Vec2;
+}
+
+impl ToVec2 for Point {
+    fn to_vec2(self) -> Vec2 {
+        Vec2::new(self.x, self.y)
+    }
+}
+
+impl ToVec2 for Vector {
+    fn to_vec2(self) -> Vec2 {
+        Vec2::new(self.x, self.y)
+    }
+}
@@ -1,41 +1,55 @@
 use lyon_tessellation::{
-    self as tess, FillVertex, FillVertexConstructor, StrokeVertex, StrokeVertexConstructor,
+    self as tess, FillVertex, FillVertexConstructor, Side, StrokeVertex, StrokeVertexConstructor,
 };
 
-use crate::brush::{Brush};
-
 /// The index type of a Bevy [`Mesh`](bevy::render::mesh::Mesh).
 type IndexType = u32;
 /// Lyon's [`VertexBuffers`] generic data type defined for [`Vertex`].
 pub type VertexBuffers = tess::VertexBuffers<Vertex, IndexType>;
 
+/// UV of the vertices that are not part of a textured stroke. The shader only
+/// samples the stroke texture where V is not negative.
+pub const UNTEXTURED_UV: [f32; 2] = [0.0, -1.0];
+
 /// A vertex with all the necessary attributes to be inserted into a Bevy
 /// [`Mesh`](bevy::render::mesh::Mesh).
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Vertex {
     pub position: [f32; 2],
+    pub uv: [f32; 2],
 }
 
-/// Zero-sized type used to implement various vertex construction traits from
-/// Lyon.
-pub struct VertexConstructor<'a> {
-    pub brush: &'a Brush,
+/// Type used to implement various vertex construction traits from Lyon.
+#[derive(Default)]
+pub struct VertexConstructor {
+    /// Factor turning the distance along a textured stroke into the U texture
+    /// coordinate. `None` for untextured geometry.
+    pub u_scale: Option<f32>,
 }
 
 /// Enables the construction of a [`Vertex`] when using a `FillTessellator`.
-impl FillVertexConstructor<Vertex> for VertexConstructor<'_> {
+impl FillVertexConstructor<Vertex> for VertexConstructor {
     fn new_vertex(&mut self, vertex: FillVertex) -> Vertex {
         Vertex {
             position: [vertex.position().x, vertex.position().y],
+            uv: UNTEXTURED_UV,
         }
     }
 }
 
 /// Enables the construction of a [`Vertex`] when using a `StrokeTessellator`.
-impl StrokeVertexConstructor<Vertex> for VertexConstructor<'_> {
+impl StrokeVertexConstructor<Vertex> for VertexConstructor {
     fn new_vertex(&mut self, vertex: StrokeVertex) -> Vertex {
+        let uv = self.u_scale.map_or(UNTEXTURED_UV, |u_scale| {
+            let v = match vertex.side() {
+                Side::Positive => 0.0,
+                Side::Negative => 1.0,
+            };
+            [vertex.advancement() * u_scale, v]
+        });
         Vertex {
             position: [vertex.position().x, vertex.position().y],
-            }
+            uv,
+        }
     }
 }